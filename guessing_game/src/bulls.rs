@@ -0,0 +1,229 @@
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use std::fmt;
+
+use crate::{engine::Engine, Config, Messages, State};
+
+pub const DIGITS: usize = 4;
+
+/// How many tries a Bulls and Cows game gets unless --attempts says otherwise.
+pub const DEFAULT_ATTEMPTS: u32 = 10;
+
+/// The score of one guess: digits in the right place are bulls, digits that
+/// are in the code but somewhere else are cows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tally {
+    pub bulls: usize,
+    pub cows: usize,
+}
+
+impl Tally {
+    pub fn message(&self, messages: &Messages) -> String {
+        let bulls = match self.bulls {
+            1 => messages.get("one-bull").to_string(),
+            n => messages.format("bulls", &[("bulls", &n)]),
+        };
+        let cows = match self.cows {
+            1 => messages.get("one-cow").to_string(),
+            n => messages.format("cows", &[("cows", &n)]),
+        };
+
+        format!("{bulls}, {cows}")
+    }
+}
+
+impl fmt::Display for Tally {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message(&Messages::english()))
+    }
+}
+
+/// Guess a code of four different digits in a limited number of tries.
+#[derive(Debug)]
+pub struct BullsAndCows {
+    code: [u8; DIGITS],
+    max_attempts: u32,
+    attempts: u32,
+    state: State,
+}
+
+impl BullsAndCows {
+    /// Starts a game with the config's attempts and seed, or a random seed if
+    /// it has none.
+    pub fn new(config: &Config) -> BullsAndCows {
+        let seed = config.seed.unwrap_or_else(|| rand::thread_rng().gen());
+
+        BullsAndCows::with_seed(config.max_attempts, seed)
+    }
+
+    pub fn with_seed(max_attempts: u32, seed: u64) -> BullsAndCows {
+        let mut digits: Vec<u8> = (0..10).collect();
+        digits.shuffle(&mut StdRng::seed_from_u64(seed));
+
+        let mut code = [0; DIGITS];
+        code.copy_from_slice(&digits[..DIGITS]);
+
+        BullsAndCows {
+            code,
+            max_attempts,
+            attempts: 0,
+            state: State::Playing,
+        }
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn score(&self, guess: &[u8; DIGITS]) -> Tally {
+        let bulls = guess
+            .iter()
+            .zip(&self.code)
+            .filter(|(guess, code)| guess == code)
+            .count();
+        let common = guess
+            .iter()
+            .filter(|digit| self.code.contains(digit))
+            .count();
+
+        Tally {
+            bulls,
+            cows: common - bulls,
+        }
+    }
+}
+
+impl Engine for BullsAndCows {
+    type Guess = [u8; DIGITS];
+    type Feedback = Tally;
+
+    fn intro(&self, messages: &Messages) -> String {
+        messages.format(
+            "bulls-intro",
+            &[("digits", &DIGITS), ("tries", &self.max_attempts)],
+        )
+    }
+
+    fn parse_guess(&self, input: &str, messages: &Messages) -> Result<[u8; DIGITS], String> {
+        let input = input.trim();
+        let mut guess = [0; DIGITS];
+
+        if input.chars().count() != DIGITS {
+            return Err(messages.format("bulls-length", &[("digits", &DIGITS)]));
+        }
+
+        for (i, c) in input.chars().enumerate() {
+            let digit = c
+                .to_digit(10)
+                .ok_or_else(|| messages.format("bulls-not-a-digit", &[("input", &c)]))?
+                as u8;
+
+            if guess[..i].contains(&digit) {
+                return Err(messages.format("bulls-repeated-digit", &[("digit", &digit)]));
+            }
+            guess[i] = digit;
+        }
+
+        Ok(guess)
+    }
+
+    fn guess(&mut self, guess: [u8; DIGITS]) -> Tally {
+        let score = self.score(&guess);
+
+        if self.state != State::Playing {
+            return score;
+        }
+
+        self.attempts += 1;
+
+        if score.bulls == DIGITS {
+            self.state = State::Won;
+        } else if self.attempts == self.max_attempts {
+            self.state = State::Lost;
+        }
+
+        score
+    }
+
+    fn describe(&self, tally: &Tally, messages: &Messages) -> String {
+        tally.message(messages)
+    }
+
+    fn state(&self) -> State {
+        self.state
+    }
+
+    fn attempts_left(&self) -> u32 {
+        self.max_attempts - self.attempts
+    }
+
+    fn answer(&self) -> String {
+        self.code.iter().map(|digit| digit.to_string()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::play_engine;
+
+    #[test]
+    fn code_has_four_different_digits() {
+        let game = BullsAndCows::with_seed(10, 1);
+        let code = game.answer();
+
+        assert_eq!(Ok(game.code), game.parse_guess(&code, &Messages::english()));
+    }
+
+    #[test]
+    fn rejects_invalid_codes() {
+        let game = BullsAndCows::with_seed(10, 1);
+        let messages = Messages::english();
+
+        assert!(game
+            .parse_guess("123", &messages)
+            .unwrap_err()
+            .contains("exactly 4"));
+        assert!(game
+            .parse_guess("12a4", &messages)
+            .unwrap_err()
+            .contains("not a digit"));
+        assert!(game
+            .parse_guess("1224", &messages)
+            .unwrap_err()
+            .contains("more than once"));
+    }
+
+    #[test]
+    fn scores_bulls_and_cows() {
+        let mut game = BullsAndCows::with_seed(10, 1);
+        game.code = [1, 2, 3, 4];
+
+        assert_eq!(Tally { bulls: 0, cows: 0 }, game.guess([5, 6, 7, 8]));
+        assert_eq!(Tally { bulls: 2, cows: 2 }, game.guess([1, 2, 4, 3]));
+        assert_eq!(Tally { bulls: 0, cows: 4 }, game.guess([4, 3, 2, 1]));
+        assert_eq!(State::Playing, game.state());
+        assert_eq!(Tally { bulls: 4, cows: 0 }, game.guess([1, 2, 3, 4]));
+        assert_eq!(State::Won, game.state());
+    }
+
+    #[test]
+    fn plays_through_the_shared_loop() {
+        let mut game = BullsAndCows::with_seed(2, 1);
+        game.code = [1, 2, 3, 4];
+        let mut output = Vec::new();
+
+        let state = play_engine(
+            &mut game,
+            &Messages::english(),
+            None,
+            "1122\n5678\n4321\n".as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert_eq!(State::Lost, state);
+        assert!(output.contains("0 bulls, 0 cows\n1 tries left."));
+        assert!(output.ends_with("You Lose! The answer was 1234.\n"));
+    }
+}
@@ -0,0 +1,257 @@
+use std::{
+    io::{self, BufRead, Write},
+    time::Instant,
+};
+
+use crate::{Game, Guess, Messages, Outcome, State, TimeLimit};
+
+/// The rules of a guessing game, without any I/O. `play_engine` runs any of
+/// them with the same input loop.
+pub trait Engine {
+    type Guess;
+    type Feedback;
+
+    /// What the player is told before the first guess.
+    fn intro(&self, messages: &Messages) -> String;
+
+    /// Checks one line of input, returning what is wrong with it if it is not
+    /// a valid guess.
+    fn parse_guess(&self, input: &str, messages: &Messages) -> Result<Self::Guess, String>;
+
+    fn guess(&mut self, guess: Self::Guess) -> Self::Feedback;
+
+    /// What the player is told about a guess. Empty if there is nothing to
+    /// say besides winning or losing.
+    fn describe(&self, feedback: &Self::Feedback, messages: &Messages) -> String;
+
+    /// Answers the player typing `hint`, or `None` if the game has no hints,
+    /// in which case the input is treated as a guess.
+    fn hint(&mut self, _messages: &Messages) -> Option<String> {
+        None
+    }
+
+    fn state(&self) -> State;
+
+    fn attempts_left(&self) -> u32;
+
+    /// The hidden answer, shown when the player loses.
+    fn answer(&self) -> String;
+
+    /// What the player is told when they lose.
+    fn reveal(&self, messages: &Messages) -> String {
+        messages.format("you-lose-answer", &[("answer", &self.answer())])
+    }
+}
+
+impl Engine for Game {
+    type Guess = u32;
+    /// The guess along with what it led to, so it can be repeated back.
+    type Feedback = (u32, Outcome);
+
+    fn intro(&self, messages: &Messages) -> String {
+        let range_and_tries = messages.format(
+            "range-and-tries",
+            &[
+                ("min", self.range().start()),
+                ("max", self.range().end()),
+                ("tries", &self.attempts_left()),
+            ],
+        );
+        let mut intro = format!("{}\n{range_and_tries}", messages.get("guess-the-number"));
+
+        if self.has_hints() {
+            intro.push('\n');
+            intro.push_str(messages.get("hint-intro"));
+        }
+
+        intro
+    }
+
+    fn parse_guess(&self, input: &str, messages: &Messages) -> Result<u32, String> {
+        Guess::parse(input, self.range())
+            .map(|guess| guess.value())
+            .map_err(|e| e.message(messages))
+    }
+
+    fn guess(&mut self, guess: u32) -> (u32, Outcome) {
+        (guess, Game::guess(self, guess))
+    }
+
+    fn describe(&self, (guess, outcome): &(u32, Outcome), messages: &Messages) -> String {
+        let mut lines = vec![messages.format("you-guessed", &[("guess", guess)])];
+
+        // Winning and losing are announced by the game loop once the state
+        // changes, so they have no text of their own.
+        match outcome {
+            Outcome::TooSmall => lines.push(messages.get("too-small").to_string()),
+            Outcome::TooHigh => lines.push(messages.get("too-high").to_string()),
+            Outcome::Win | Outcome::Lose => {}
+            Outcome::Repeated => lines.push(messages.format("repeated", &[("guess", guess)])),
+        }
+
+        if let Some(hint) = self.last_hint() {
+            lines.push(hint.message(messages));
+        }
+
+        lines.join("\n")
+    }
+
+    fn hint(&mut self, messages: &Messages) -> Option<String> {
+        if !self.has_hints() {
+            return None;
+        }
+
+        let hint = match self.parity_hint() {
+            Ok(parity) => messages.format(
+                "parity-hint",
+                &[
+                    ("parity", &parity.message(messages)),
+                    ("tries", &self.attempts_left()),
+                ],
+            ),
+            Err(e) => e.message(messages).to_string(),
+        };
+
+        Some(hint)
+    }
+
+    fn state(&self) -> State {
+        Game::state(self)
+    }
+
+    fn attempts_left(&self) -> u32 {
+        Game::attempts_left(self)
+    }
+
+    fn answer(&self) -> String {
+        self.secret_number().to_string()
+    }
+
+    fn reveal(&self, messages: &Messages) -> String {
+        messages.format("you-lose", &[("answer", &self.secret_number())])
+    }
+}
+
+/// Plays any `Engine` to the end, reading guesses from `input` one per line
+/// and writing everything the player sees to `output` in the language of
+/// `messages`. With a `time_limit`, `input` is expected to time out (see
+/// `TimedInput`), which loses the game.
+pub fn play_engine<E: Engine>(
+    engine: &mut E,
+    messages: &Messages,
+    time_limit: Option<TimeLimit>,
+    mut input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<State> {
+    writeln!(output, "{}", engine.intro(messages))?;
+    if let Some(limit) = time_limit {
+        let id = match limit {
+            TimeLimit::Total(_) => "time-limit-total",
+            TimeLimit::PerGuess(_) => "time-limit-per-guess",
+        };
+        let seconds = limit.duration().as_secs();
+        writeln!(output, "{}", messages.format(id, &[("seconds", &seconds)]))?;
+    }
+
+    let started = Instant::now();
+
+    loop {
+        writeln!(output, "{}", messages.get("input-guess"))?;
+
+        let mut line = String::new();
+
+        let read = match input.read_line(&mut line) {
+            Err(e) if e.kind() == io::ErrorKind::TimedOut => {
+                writeln!(output, "{}", messages.get("time-up"))?;
+                writeln!(output, "{}", engine.reveal(messages))?;
+                return Ok(State::Lost);
+            }
+            read => read?,
+        };
+
+        if read == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "no-more-guesses",
+            ));
+        }
+
+        if line.trim() == "hint" {
+            if let Some(hint) = engine.hint(messages) {
+                writeln!(output, "{hint}")?;
+                continue;
+            }
+        }
+
+        // handling invalid input
+        let guess = match engine.parse_guess(&line, messages) {
+            Ok(guess) => guess,
+            Err(e) => {
+                writeln!(output, "{e}")?;
+                continue;
+            }
+        };
+
+        let feedback = engine.guess(guess);
+        let feedback = engine.describe(&feedback, messages);
+        if !feedback.is_empty() {
+            writeln!(output, "{feedback}")?;
+        }
+
+        match engine.state() {
+            State::Won => {
+                writeln!(output, "{}", messages.get("you-win"))?;
+                return Ok(State::Won);
+            }
+            State::Lost => {
+                writeln!(output, "{}", engine.reveal(messages))?;
+                return Ok(State::Lost);
+            }
+            State::Playing => {
+                let tries = engine.attempts_left();
+                writeln!(
+                    output,
+                    "{}",
+                    messages.format("tries-left", &[("tries", &tries)])
+                )?;
+            }
+        }
+
+        if let Some(TimeLimit::Total(limit)) = time_limit {
+            let seconds = limit.saturating_sub(started.elapsed()).as_secs();
+            writeln!(
+                output,
+                "{}",
+                messages.format("time-left", &[("seconds", &seconds)])
+            )?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Config;
+
+    #[test]
+    fn number_game_runs_through_the_shared_loop() {
+        let config = Config::preset("easy").unwrap();
+        let mut game = Game::with_seed(&config, 5);
+        let input = format!("0\n{}\n", game.secret_number());
+        let mut output = Vec::new();
+
+        let state = play_engine(
+            &mut game,
+            &Messages::english(),
+            None,
+            input.as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert_eq!(State::Won, state);
+        assert!(output.contains("between 1 and 50"));
+        assert!(output.ends_with("You Win!\n"));
+    }
+}
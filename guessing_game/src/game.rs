@@ -0,0 +1,252 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{cmp::Ordering, collections::HashSet, ops::RangeInclusive};
+
+use crate::{
+    hints::{Hint, HintError, Hints, Parity},
+    Config,
+};
+
+/// What a single guess led to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    TooSmall,
+    TooHigh,
+    Win,
+    /// The guess was wrong and it was the last attempt.
+    Lose,
+    /// The number was guessed before, so it didn't use up an attempt.
+    Repeated,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    Playing,
+    Won,
+    Lost,
+}
+
+/// One round of the number guessing game, without any I/O.
+#[derive(Debug)]
+pub struct Game {
+    secret_number: u32,
+    range: RangeInclusive<u32>,
+    max_attempts: u32,
+    attempts: u32,
+    guessed: HashSet<u32>,
+    history: Vec<(u32, Outcome)>,
+    parity_hint_used: bool,
+    /// Set in hint mode, to give warmer/colder feedback.
+    hints: Option<Hints>,
+    last_hint: Option<Hint>,
+    state: State,
+    seed: u64,
+}
+
+impl Game {
+    /// Starts a game with the config's seed, or a random one if it has none.
+    pub fn new(config: &Config) -> Game {
+        let seed = config.seed.unwrap_or_else(|| rand::thread_rng().gen());
+
+        Game::with_seed(config, seed)
+    }
+
+    /// Starts a game whose secret number is picked by an RNG seeded with
+    /// `seed`, so the same seed and config always give the same number.
+    pub fn with_seed(config: &Config, seed: u64) -> Game {
+        let secret_number = StdRng::seed_from_u64(seed).gen_range(config.range.clone());
+
+        Game {
+            secret_number,
+            range: config.range.clone(),
+            max_attempts: config.max_attempts,
+            attempts: 0,
+            guessed: HashSet::new(),
+            history: Vec::new(),
+            parity_hint_used: false,
+            hints: config.hints.then(Hints::default),
+            last_hint: None,
+            state: State::Playing,
+            seed,
+        }
+    }
+
+    /// Compares `guess` with the secret number and uses up an attempt, unless
+    /// the same number was guessed before. Once the game is over, further
+    /// guesses are ignored and return the final outcome again.
+    pub fn guess(&mut self, guess: u32) -> Outcome {
+        match self.state {
+            State::Won => return Outcome::Win,
+            State::Lost => return Outcome::Lose,
+            State::Playing => {}
+        }
+
+        let outcome = self.compare(guess);
+        self.history.push((guess, outcome));
+
+        // Only a wrong guess that leaves the game running gets a hint.
+        self.last_hint = match (&mut self.hints, outcome) {
+            (Some(hints), Outcome::TooSmall | Outcome::TooHigh) => {
+                Some(hints.after_guess(guess, self.secret_number, &self.range))
+            }
+            _ => None,
+        };
+
+        outcome
+    }
+
+    fn compare(&mut self, guess: u32) -> Outcome {
+        if !self.guessed.insert(guess) {
+            return Outcome::Repeated;
+        }
+
+        self.attempts += 1;
+
+        let outcome = match guess.cmp(&self.secret_number) {
+            Ordering::Less => Outcome::TooSmall,
+            Ordering::Greater => Outcome::TooHigh,
+            Ordering::Equal => {
+                self.state = State::Won;
+                return Outcome::Win;
+            }
+        };
+
+        if self.attempts_left() == 0 {
+            self.state = State::Lost;
+            return Outcome::Lose;
+        }
+
+        outcome
+    }
+
+    /// Tells whether the secret number is even or odd, once per game, at the
+    /// cost of an attempt.
+    pub fn parity_hint(&mut self) -> Result<Parity, HintError> {
+        if self.state != State::Playing {
+            return Err(HintError::GameOver);
+        }
+        if self.parity_hint_used {
+            return Err(HintError::AlreadyUsed);
+        }
+        if self.attempts_left() == 1 {
+            return Err(HintError::LastAttempt);
+        }
+
+        self.parity_hint_used = true;
+        self.attempts += 1;
+
+        Ok(Parity::of(self.secret_number))
+    }
+
+    /// Whether the game was started in hint mode.
+    pub fn has_hints(&self) -> bool {
+        self.hints.is_some()
+    }
+
+    /// Warmer/colder feedback on the last guess, in hint mode.
+    pub fn last_hint(&self) -> Option<Hint> {
+        self.last_hint
+    }
+
+    /// Every guess made while the game was running, with its outcome.
+    pub fn history(&self) -> &[(u32, Outcome)] {
+        &self.history
+    }
+
+    /// The seed the secret number was picked with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn range(&self) -> &RangeInclusive<u32> {
+        &self.range
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn attempts_left(&self) -> u32 {
+        self.max_attempts - self.attempts
+    }
+
+    /// The number to guess. Only meant to be shown once the game is over.
+    pub fn secret_number(&self) -> u32 {
+        self.secret_number
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(max_attempts: u32) -> Game {
+        let config = Config {
+            max_attempts,
+            ..Config::preset("normal").unwrap()
+        };
+
+        Game::with_seed(&config, 42)
+    }
+
+    #[test]
+    fn same_seed_gives_same_secret_number() {
+        assert_eq!(game(7).secret_number(), game(7).secret_number());
+        assert!((1..=100).contains(&game(7).secret_number()));
+    }
+
+    #[test]
+    fn too_small_and_too_high_use_up_attempts() {
+        let mut game = game(7);
+        let secret_number = game.secret_number();
+
+        assert_eq!(Outcome::TooSmall, game.guess(secret_number - 1));
+        assert_eq!(Outcome::TooHigh, game.guess(secret_number + 1));
+        assert_eq!(5, game.attempts_left());
+        assert_eq!(State::Playing, game.state());
+    }
+
+    #[test]
+    fn repeated_guess_is_free() {
+        let mut game = game(7);
+        let secret_number = game.secret_number();
+
+        assert_eq!(Outcome::TooHigh, game.guess(secret_number + 1));
+        assert_eq!(Outcome::Repeated, game.guess(secret_number + 1));
+        assert_eq!(6, game.attempts_left());
+    }
+
+    #[test]
+    fn parity_hint_costs_an_attempt_and_works_once() {
+        let mut last_try = game(1);
+        let mut game = game(3);
+
+        assert_eq!(Ok(Parity::of(game.secret_number())), game.parity_hint());
+        assert_eq!(2, game.attempts_left());
+        assert_eq!(Err(HintError::AlreadyUsed), game.parity_hint());
+        assert_eq!(Err(HintError::LastAttempt), last_try.parity_hint());
+    }
+
+    #[test]
+    fn right_guess_wins() {
+        let mut game = game(7);
+
+        assert_eq!(Outcome::Win, game.guess(game.secret_number()));
+        assert_eq!(State::Won, game.state());
+        assert_eq!(1, game.attempts());
+    }
+
+    #[test]
+    fn last_wrong_guess_loses_and_game_stays_over() {
+        let mut game = game(2);
+        let secret_number = game.secret_number();
+
+        assert_eq!(Outcome::TooHigh, game.guess(secret_number + 2));
+        assert_eq!(Outcome::Lose, game.guess(secret_number + 1));
+        assert_eq!(Outcome::Lose, game.guess(secret_number));
+        assert_eq!(2, game.attempts());
+    }
+}
@@ -0,0 +1,101 @@
+use std::{fmt, num::IntErrorKind, ops::RangeInclusive};
+
+use crate::Messages;
+
+// The same idea as the Guess type in error_handling: once a Guess exists, its
+// value is known to be in range, so the game never has to check it again.
+// Instead of panicking it returns an error the player can be shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Guess {
+    value: u32,
+}
+
+/// Why a line of input is not a valid guess.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuessError {
+    NotANumber(String),
+    Negative,
+    OutOfRange { min: u32, max: u32 },
+}
+
+impl GuessError {
+    pub fn message(&self, messages: &Messages) -> String {
+        match self {
+            GuessError::NotANumber(input) => messages.format("not-a-number", &[("input", input)]),
+            GuessError::Negative => messages.get("negative").to_string(),
+            GuessError::OutOfRange { min, max } => {
+                messages.format("out-of-range", &[("min", min), ("max", max)])
+            }
+        }
+    }
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message(&Messages::english()))
+    }
+}
+
+impl std::error::Error for GuessError {}
+
+impl Guess {
+    pub fn new(value: i64, range: &RangeInclusive<u32>) -> Result<Guess, GuessError> {
+        if value < 0 {
+            return Err(GuessError::Negative);
+        }
+
+        match u32::try_from(value) {
+            Ok(value) if range.contains(&value) => Ok(Guess { value }),
+            _ => Err(GuessError::OutOfRange {
+                min: *range.start(),
+                max: *range.end(),
+            }),
+        }
+    }
+
+    pub fn parse(input: &str, range: &RangeInclusive<u32>) -> Result<Guess, GuessError> {
+        let input = input.trim();
+
+        match input.parse::<i64>() {
+            Ok(value) => Guess::new(value, range),
+            Err(e) => match e.kind() {
+                IntErrorKind::NegOverflow => Err(GuessError::Negative),
+                IntErrorKind::PosOverflow => Guess::new(i64::MAX, range),
+                _ => Err(GuessError::NotANumber(input.to_string())),
+            },
+        }
+    }
+
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_numbers_in_range() {
+        assert_eq!(42, Guess::parse(" 42\n", &(1..=100)).unwrap().value());
+    }
+
+    #[test]
+    fn explains_what_is_wrong() {
+        let range = 1..=100;
+
+        assert_eq!(
+            Err(GuessError::NotANumber(String::from("forty"))),
+            Guess::parse("forty", &range)
+        );
+        assert_eq!(Err(GuessError::Negative), Guess::parse("-3", &range));
+        assert_eq!(
+            Err(GuessError::OutOfRange { min: 1, max: 100 }),
+            Guess::parse("101", &range)
+        );
+        assert_eq!(
+            Err(GuessError::OutOfRange { min: 1, max: 100 }),
+            Guess::parse("99999999999999999999", &range)
+        );
+    }
+}
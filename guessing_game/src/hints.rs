@@ -0,0 +1,204 @@
+use std::{cmp::Ordering, fmt, ops::RangeInclusive};
+
+use crate::Messages;
+
+/// How close a guess is to the secret number, as a share of the whole range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Band {
+    Burning,
+    Hot,
+    Warm,
+    Cold,
+    Freezing,
+}
+
+impl Band {
+    pub fn new(distance: u32, range: &RangeInclusive<u32>) -> Band {
+        let size = u64::from(range.end() - range.start()) + 1;
+        let percent = u64::from(distance) * 100 / size;
+
+        match percent {
+            0..=4 => Band::Burning,
+            5..=9 => Band::Hot,
+            10..=24 => Band::Warm,
+            25..=49 => Band::Cold,
+            _ => Band::Freezing,
+        }
+    }
+
+    pub fn message<'a>(&self, messages: &Messages) -> &'a str {
+        messages.get(match self {
+            Band::Burning => "burning",
+            Band::Hot => "hot",
+            Band::Warm => "warm",
+            Band::Cold => "cold",
+            Band::Freezing => "freezing",
+        })
+    }
+}
+
+impl fmt::Display for Band {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message(&Messages::english()))
+    }
+}
+
+/// Whether a guess got closer than the one before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trend {
+    Warmer,
+    Colder,
+    Same,
+}
+
+impl Trend {
+    pub fn message<'a>(&self, messages: &Messages) -> &'a str {
+        messages.get(match self {
+            Trend::Warmer => "warmer",
+            Trend::Colder => "colder",
+            Trend::Same => "same-distance",
+        })
+    }
+}
+
+impl fmt::Display for Trend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message(&Messages::english()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parity {
+    Even,
+    Odd,
+}
+
+impl Parity {
+    pub fn of(number: u32) -> Parity {
+        if number.is_multiple_of(2) {
+            Parity::Even
+        } else {
+            Parity::Odd
+        }
+    }
+
+    pub fn message<'a>(&self, messages: &Messages) -> &'a str {
+        messages.get(match self {
+            Parity::Even => "even",
+            Parity::Odd => "odd",
+        })
+    }
+}
+
+impl fmt::Display for Parity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message(&Messages::english()))
+    }
+}
+
+/// Why the parity hint can't be given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HintError {
+    AlreadyUsed,
+    /// The hint costs an attempt, and taking the last one would end the game.
+    LastAttempt,
+    GameOver,
+}
+
+impl HintError {
+    pub fn message<'a>(&self, messages: &Messages) -> &'a str {
+        messages.get(match self {
+            HintError::AlreadyUsed => "hint-used",
+            HintError::LastAttempt => "hint-last-try",
+            HintError::GameOver => "game-over",
+        })
+    }
+}
+
+impl fmt::Display for HintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message(&Messages::english()))
+    }
+}
+
+impl std::error::Error for HintError {}
+
+/// Warmer/colder feedback for one wrong guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hint {
+    pub band: Band,
+    /// `None` for the first guess, which has nothing to compare with.
+    pub trend: Option<Trend>,
+}
+
+impl Hint {
+    pub fn message(&self, messages: &Messages) -> String {
+        let band = self.band.message(messages);
+
+        match self.trend {
+            Some(trend) => format!("{band} {}", trend.message(messages)),
+            None => band.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message(&Messages::english()))
+    }
+}
+
+/// Remembers how far the previous guess was so the next one can be compared.
+#[derive(Debug, Default)]
+pub struct Hints {
+    previous_distance: Option<u32>,
+}
+
+impl Hints {
+    pub fn after_guess(
+        &mut self,
+        guess: u32,
+        secret_number: u32,
+        range: &RangeInclusive<u32>,
+    ) -> Hint {
+        let distance = guess.abs_diff(secret_number);
+
+        let trend = self
+            .previous_distance
+            .map(|previous| match distance.cmp(&previous) {
+                Ordering::Less => Trend::Warmer,
+                Ordering::Greater => Trend::Colder,
+                Ordering::Equal => Trend::Same,
+            });
+        self.previous_distance = Some(distance);
+
+        Hint {
+            band: Band::new(distance, range),
+            trend,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bands_scale_with_the_range() {
+        assert_eq!(Band::Burning, Band::new(2, &(1..=100)));
+        assert_eq!(Band::Warm, Band::new(20, &(1..=100)));
+        assert_eq!(Band::Freezing, Band::new(60, &(1..=100)));
+        assert_eq!(Band::Hot, Band::new(60, &(1..=1000)));
+    }
+
+    #[test]
+    fn compares_each_guess_with_the_previous_one() {
+        let mut hints = Hints::default();
+        let range = 1..=100;
+
+        assert_eq!(None, hints.after_guess(10, 50, &range).trend);
+        assert_eq!(Some(Trend::Warmer), hints.after_guess(40, 50, &range).trend);
+        assert_eq!(Some(Trend::Same), hints.after_guess(60, 50, &range).trend);
+        assert_eq!(Some(Trend::Colder), hints.after_guess(90, 50, &range).trend);
+    }
+}
@@ -0,0 +1,599 @@
+use std::{
+    env,
+    error::Error,
+    io::{self, BufRead},
+    ops::RangeInclusive,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+pub mod bulls;
+pub mod engine;
+mod game;
+mod guess;
+pub mod hints;
+mod messages;
+pub mod recording;
+pub mod scores;
+pub mod server;
+pub mod solver;
+pub mod timer;
+
+pub use bulls::BullsAndCows;
+pub use engine::{play_engine, Engine};
+pub use game::{Game, Outcome, State};
+pub use guess::{Guess, GuessError};
+pub use hints::Hints;
+pub use messages::Messages;
+pub use recording::{Recorder, Recording};
+pub use scores::Score;
+pub use server::Server;
+pub use timer::{TimeLimit, TimedInput};
+
+pub struct Config {
+    pub range: RangeInclusive<u32>,
+    pub max_attempts: u32,
+    /// The preset the game was built from, or "custom" when the range or the
+    /// number of attempts was changed. Scores are grouped by it.
+    pub difficulty: String,
+    pub player: String,
+    pub scores_path: PathBuf,
+    /// The address `serve` listens on.
+    pub addr: String,
+    /// Give warmer/colder feedback and allow one parity hint.
+    pub hints: bool,
+    /// Pick the secret number with this seed so the game can be reproduced.
+    pub seed: Option<u64>,
+    /// Save the played game to this file so it can be replayed.
+    pub record_path: Option<PathBuf>,
+    pub variant: Variant,
+    /// The language the player is spoken to in, such as `es` or a locale
+    /// like `es_ES.UTF-8`. Anything without a catalog gets English.
+    pub lang: String,
+    /// Play against the clock, for the whole game or for each guess.
+    pub time_limit: Option<TimeLimit>,
+    pub mode: Mode,
+}
+
+/// Which game `Mode::Play` plays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    /// Guess the secret number with too small/too high feedback.
+    Number,
+    /// Guess a four-digit code with bulls/cows feedback.
+    BullsAndCows,
+}
+
+/// What the program does once the arguments are parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mode {
+    Play,
+    /// Show the high-score table instead of playing.
+    Scores,
+    /// Let the computer guess a secret number it picked itself.
+    Solve,
+    /// Let the computer guess a number the player is thinking of.
+    SolveHuman,
+    /// Host a game that players join over TCP.
+    Serve,
+    /// Play a recorded game again and check it ends the same way.
+    Replay(PathBuf),
+}
+
+impl Config {
+    // Start from the normal preset, then let --difficulty replace it and
+    // --min, --max and --attempts adjust single settings. Errors are message
+    // IDs, so they can be shown in the player's language.
+    pub fn build(args: &[String]) -> Result<Config, &'static str> {
+        let mut config = Config::preset("normal").unwrap();
+        let mut min = None;
+        let mut max = None;
+        let mut max_attempts = None;
+        let mut player = None;
+        let mut scores_path = None;
+        let mut addr = None;
+        let mut hints = false;
+        let mut seed = None;
+        let mut record_path = None;
+        let mut variant = Variant::Number;
+        let mut lang = None;
+        let mut time_limit = None;
+        let mut mode = Mode::Play;
+
+        let mut args = args.iter().skip(1);
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or("option-needs-value");
+
+            match arg.as_str() {
+                "--difficulty" => {
+                    config = Config::preset(value()?).ok_or("difficulty-invalid")?;
+                }
+                "--min" => min = Some(value()?.parse().map_err(|_| "min-not-a-number")?),
+                "--max" => max = Some(value()?.parse().map_err(|_| "max-not-a-number")?),
+                "--attempts" => {
+                    max_attempts = Some(value()?.parse().map_err(|_| "attempts-not-a-number")?)
+                }
+                "--name" => player = Some(value()?.clone()),
+                "--scores-file" => scores_path = Some(PathBuf::from(value()?)),
+                "--scores" => mode = Mode::Scores,
+                "--solve" => mode = Mode::Solve,
+                "--solve-human" => mode = Mode::SolveHuman,
+                "serve" => mode = Mode::Serve,
+                "--addr" => addr = Some(value()?.clone()),
+                "--hints" => hints = true,
+                "--seed" => seed = Some(value()?.parse().map_err(|_| "seed-not-a-number")?),
+                "--record" => record_path = Some(PathBuf::from(value()?)),
+                "--variant" => {
+                    variant = match value()?.as_str() {
+                        "number" => Variant::Number,
+                        "bulls-and-cows" | "bulls" => Variant::BullsAndCows,
+                        _ => return Err("variant-invalid"),
+                    }
+                }
+                "--lang" => lang = Some(value()?.clone()),
+                "--time" | "--time-per-guess" => {
+                    if time_limit.is_some() {
+                        return Err("time-limits-together");
+                    }
+
+                    let seconds: u64 = value()?.parse().map_err(|_| "time-limit-not-a-number")?;
+                    if seconds == 0 {
+                        return Err("time-limit-zero");
+                    }
+
+                    let duration = Duration::from_secs(seconds);
+                    time_limit = Some(if arg == "--time" {
+                        TimeLimit::Total(duration)
+                    } else {
+                        TimeLimit::PerGuess(duration)
+                    });
+                }
+                "--replay" => mode = Mode::Replay(PathBuf::from(value()?)),
+                _ => return Err("unknown-option"),
+            }
+        }
+
+        if variant != Variant::Number {
+            if time_limit.is_some() {
+                return Err("time-limit-needs-number");
+            }
+            if hints {
+                return Err("hints-need-number");
+            }
+            if record_path.is_some() {
+                return Err("record-needs-number");
+            }
+        }
+
+        if min.is_some() || max.is_some() || max_attempts.is_some() {
+            config.difficulty = String::from("custom");
+        }
+
+        let min = min.unwrap_or(*config.range.start());
+        let max = max.unwrap_or(*config.range.end());
+        if min >= max {
+            return Err("min-not-below-max");
+        }
+        config.range = min..=max;
+
+        if let Some(max_attempts) = max_attempts {
+            if max_attempts == 0 {
+                return Err("attempts-zero");
+            }
+            config.max_attempts = max_attempts;
+        } else if variant == Variant::BullsAndCows {
+            // The number presets are too short for a code, so only an
+            // explicit --attempts changes the default.
+            config.max_attempts = bulls::DEFAULT_ATTEMPTS;
+        }
+
+        if let Some(player) = player {
+            config.player = player;
+        }
+        if let Some(scores_path) = scores_path {
+            config.scores_path = scores_path;
+        }
+        if let Some(addr) = addr {
+            config.addr = addr;
+        }
+        config.hints = hints;
+        config.seed = seed;
+        config.record_path = record_path;
+        config.variant = variant;
+        // Like the name, the language comes from the environment unless it is
+        // given. Presets stay English so tests don't depend on the locale.
+        config.lang = lang
+            .or_else(|| env::var("LANG").ok())
+            .unwrap_or(config.lang);
+        config.time_limit = time_limit;
+        config.mode = mode;
+
+        Ok(config)
+    }
+
+    /// The language `build` would pick from these arguments, for messages
+    /// that are needed before the arguments are known to be valid.
+    pub fn lang(args: &[String]) -> String {
+        let given = args
+            .windows(2)
+            .rev()
+            .find(|pair| pair[0] == "--lang")
+            .map(|pair| pair[1].clone());
+
+        given
+            .or_else(|| env::var("LANG").ok())
+            .unwrap_or_else(|| String::from("en"))
+    }
+
+    pub fn preset(difficulty: &str) -> Option<Config> {
+        let (range, max_attempts) = match difficulty {
+            "easy" => (1..=50, 10),
+            "normal" => (1..=100, 7),
+            "hard" => (1..=1000, 10),
+            _ => return None,
+        };
+
+        Some(Config {
+            range,
+            max_attempts,
+            difficulty: difficulty.to_string(),
+            player: env::var("USER").unwrap_or_else(|_| String::from("anonymous")),
+            scores_path: PathBuf::from(scores::DEFAULT_PATH),
+            addr: String::from(server::DEFAULT_ADDR),
+            hints: false,
+            seed: None,
+            record_path: None,
+            variant: Variant::Number,
+            lang: String::from("en"),
+            time_limit: None,
+            mode: Mode::Play,
+        })
+    }
+}
+
+pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
+    let messages = Messages::for_lang(&config.lang);
+
+    match &config.mode {
+        Mode::Play => match config.variant {
+            Variant::Number => play_number(&config, &messages)?,
+            Variant::BullsAndCows => play_bulls_and_cows(&config, &messages)?,
+        },
+        Mode::Scores => {
+            let table = scores::load(&config.scores_path)?;
+            table.render(&messages, io::stdout())?;
+        }
+        Mode::Solve => {
+            let mut game = Game::new(&config);
+            solver::solve(&mut game, &messages, io::stdout())?;
+        }
+        Mode::SolveHuman => {
+            solver::solve_human(&config.range, &messages, io::stdin().lock(), io::stdout())?;
+        }
+        Mode::Serve => {
+            let server = Server::bind(&config.addr, &config)?;
+            let addr = server.listener().local_addr()?;
+            println!("{}", messages.format("listening", &[("addr", &addr)]));
+
+            let winner = server.run()?;
+            println!(
+                "{}",
+                messages.format(
+                    "server-winner",
+                    &[("player", &winner.name), ("number", &winner.number)]
+                )
+            );
+        }
+        Mode::Replay(path) => {
+            let id = match Recording::load(path)?.replay(&config.lang, io::stdout())? {
+                Some(State::Won) => "replay-won",
+                Some(_) => "replay-lost",
+                None => "replay-unfinished",
+            };
+            println!("{}", messages.get(id));
+        }
+    }
+
+    Ok(())
+}
+
+fn play_number(config: &Config, messages: &Messages) -> Result<(), Box<dyn Error>> {
+    let mut game = Game::new(config);
+    let started = Instant::now();
+
+    // A time limit needs input that can stop waiting for the player.
+    let stdin: Box<dyn BufRead> = match config.time_limit {
+        Some(limit) => Box::new(TimedInput::new(io::BufReader::new(io::stdin()), limit)),
+        None => Box::new(io::stdin().lock()),
+    };
+
+    // println!("The secret number is: {}", game.secret_number());
+    let state = match &config.record_path {
+        Some(path) => {
+            let mut input = Recorder::new(stdin);
+            let state = play_engine(
+                &mut game,
+                messages,
+                config.time_limit,
+                &mut input,
+                io::stdout(),
+            );
+
+            // Save the recording even if the game was cut short, since
+            // that may be the bug being reported.
+            Recording::new(&game, config, input).save(path)?;
+            state?
+        }
+        None => play_engine(&mut game, messages, config.time_limit, stdin, io::stdout())?,
+    };
+    let elapsed = started.elapsed();
+
+    let score = Score {
+        player: config.player.clone(),
+        difficulty: config.difficulty.clone(),
+        range: config.range.clone(),
+        attempts: game.attempts(),
+        elapsed,
+        won: state == State::Won,
+    };
+
+    // Losing the score shouldn't spoil the game that was just played.
+    if let Err(e) = scores::append(&config.scores_path, &score) {
+        eprintln!("{}", messages.format("score-not-saved", &[("error", &e)]));
+    }
+
+    if config.time_limit.is_some() {
+        let seconds = format!("{:.1}", elapsed.as_secs_f64());
+        let points = timer::points(score.won, elapsed, game.attempts_left());
+
+        println!("{}", messages.format("elapsed", &[("seconds", &seconds)]));
+        println!("{}", messages.format("points", &[("points", &points)]));
+    }
+
+    Ok(())
+}
+
+fn play_bulls_and_cows(config: &Config, messages: &Messages) -> Result<(), Box<dyn Error>> {
+    let mut game = BullsAndCows::new(config);
+    let started = Instant::now();
+
+    let state = play_engine(&mut game, messages, None, io::stdin().lock(), io::stdout())?;
+
+    let score = Score {
+        player: config.player.clone(),
+        difficulty: String::from("bulls-and-cows"),
+        range: 0..=9,
+        attempts: game.attempts(),
+        elapsed: started.elapsed(),
+        won: state == State::Won,
+    };
+
+    if let Err(e) = scores::append(&config.scores_path, &score) {
+        eprintln!("{}", messages.format("score-not-saved", &[("error", &e)]));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play_with(input: &str) -> (io::Result<State>, String) {
+        let config = Config::preset("normal").unwrap();
+        play_config(&config, input)
+    }
+
+    fn play_config(config: &Config, input: &str) -> (io::Result<State>, String) {
+        play_input(config, input.as_bytes())
+    }
+
+    fn play_input(config: &Config, input: impl BufRead) -> (io::Result<State>, String) {
+        let mut game = Game::with_seed(config, 7);
+        let messages = Messages::for_lang(&config.lang);
+        let mut output = Vec::new();
+
+        let state = play_engine(&mut game, &messages, config.time_limit, input, &mut output);
+
+        (state, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn skips_invalid_input_and_wins() {
+        let config = Config::preset("normal").unwrap();
+        let secret_number = Game::with_seed(&config, 7).secret_number();
+        let too_high = secret_number + 1;
+        let input = format!("abc\n-3\n0\n{too_high}\n{too_high}\n{secret_number}\n");
+
+        let (outcome, output) = play_with(&input);
+
+        assert_eq!(State::Won, outcome.unwrap());
+        assert!(output.contains("\"abc\" is not a number"));
+        assert!(output.contains("never negative"));
+        assert!(output.contains("must be between 1 and 100"));
+        assert!(output.contains("Too High!\n6 tries left."));
+        assert!(output.contains("doesn't count.\n6 tries left."));
+        assert!(output.ends_with("You Win!\n"));
+    }
+
+    #[test]
+    fn reveals_secret_number_on_loss() {
+        let config = Config::preset("normal").unwrap();
+        let secret_number = Game::with_seed(&config, 7).secret_number();
+        let wrong: String = (1..=100)
+            .filter(|&guess| guess != secret_number)
+            .take(7)
+            .map(|guess| format!("{guess}\n"))
+            .collect();
+
+        let (outcome, output) = play_with(&wrong);
+
+        assert_eq!(State::Lost, outcome.unwrap());
+        assert!(output.ends_with(&format!("The secret number was {secret_number}.\n")));
+    }
+
+    #[test]
+    fn hint_mode_gives_feedback_and_one_parity_hint() {
+        let config = Config {
+            hints: true,
+            ..Config::preset("normal").unwrap()
+        };
+        let secret_number = Game::with_seed(&config, 7).secret_number();
+        let far = if secret_number > 50 { 1 } else { 100 };
+        let input = format!("{far}\nhint\nhint\n{secret_number}\n");
+
+        let (outcome, output) = play_config(&config, &input);
+
+        assert_eq!(State::Won, outcome.unwrap());
+        assert!(
+            output.contains("Too Small!\nFreezing!\n") || output.contains("Too High!\nFreezing!\n")
+        );
+        assert!(output.contains(&format!(
+            "The secret number is {}. 5 tries left.",
+            hints::Parity::of(secret_number)
+        )));
+        assert!(output.contains("You already used your hint."));
+    }
+
+    #[test]
+    fn hint_is_a_plain_invalid_guess_without_hint_mode() {
+        let (_, output) = play_with("hint\n");
+
+        assert!(output.contains("\"hint\" is not a number"));
+    }
+
+    #[test]
+    fn speaks_the_configured_language() {
+        let config = Config {
+            lang: String::from("es_ES.UTF-8"),
+            ..Config::preset("normal").unwrap()
+        };
+        let secret_number = Game::with_seed(&config, 7).secret_number();
+        let input = format!("abc\n{}\n{secret_number}\n", secret_number ^ 1);
+
+        let (outcome, output) = play_config(&config, &input);
+
+        assert_eq!(State::Won, outcome.unwrap());
+        assert!(output.starts_with("¡Adivina el número!\nEstá entre 1 y 100"));
+        assert!(output.contains("\"abc\" no es un número"));
+        assert!(output.contains("Te quedan 6 intentos."));
+        assert!(output.ends_with("¡Has ganado!\n"));
+    }
+
+    #[test]
+    fn running_out_of_time_loses_the_game() {
+        let config = Config {
+            time_limit: Some(TimeLimit::Total(Duration::from_secs(60))),
+            ..Config::preset("normal").unwrap()
+        };
+        let secret_number = Game::with_seed(&config, 7).secret_number();
+        let input = format!("{}\n", secret_number ^ 1);
+
+        // Input that has nothing more to give once the first line is read,
+        // as if the player stopped typing and the clock ran out.
+        let timed_out = io::Read::chain(input.as_bytes(), TimesOut);
+        let (state, output) = play_input(&config, io::BufReader::new(timed_out));
+
+        assert_eq!(State::Lost, state.unwrap());
+        assert!(output.contains("You have 60 seconds to find it."));
+        assert!(
+            output.contains("6 tries left.\n59s left on the clock.")
+                || output.contains("6 tries left.\n60s left on the clock.")
+        );
+        assert!(output.ends_with(&format!(
+            "Time's up!\nYou Lose! The secret number was {secret_number}.\n"
+        )));
+    }
+
+    struct TimesOut;
+
+    impl io::Read for TimesOut {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::TimedOut, "time is up"))
+        }
+    }
+
+    #[test]
+    fn running_out_of_input_is_an_error() {
+        let (outcome, _) = play_with("");
+
+        assert_eq!(io::ErrorKind::UnexpectedEof, outcome.unwrap_err().kind());
+    }
+
+    #[test]
+    fn builds_config_from_presets_and_overrides() {
+        let args: Vec<String> = ["guessing_game", "--difficulty", "hard", "--attempts", "3"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+
+        let config = Config::build(&args).unwrap();
+
+        assert_eq!(1..=1000, config.range);
+        assert_eq!(3, config.max_attempts);
+    }
+
+    #[test]
+    fn lang_option_beats_the_environment() {
+        let args: Vec<String> = ["guessing_game", "--lang", "es"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+
+        assert_eq!("es", Config::build(&args).unwrap().lang);
+        assert_eq!("es", Config::lang(&args));
+    }
+
+    #[test]
+    fn argument_errors_are_shown_in_the_chosen_language() {
+        let args: Vec<String> = ["guessing_game", "--lang", "es", "--min", "ten"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+
+        let err = Config::build(&args).err().unwrap();
+        let messages = Messages::for_lang(&Config::lang(&args));
+
+        assert_eq!("--min tiene que ser un número", messages.get(err));
+    }
+
+    #[test]
+    fn builds_time_limits() {
+        let build = |args: &[&str]| {
+            let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+            Config::build(&args).map(|config| config.time_limit)
+        };
+
+        assert_eq!(
+            Ok(Some(TimeLimit::PerGuess(Duration::from_secs(5)))),
+            build(&["guessing_game", "--time-per-guess", "5"])
+        );
+        assert!(build(&["guessing_game", "--time", "0"]).is_err());
+        assert!(build(&["guessing_game", "--time", "9", "--time-per-guess", "5"]).is_err());
+        assert!(build(&["guessing_game", "--variant", "bulls", "--time", "9"]).is_err());
+    }
+
+    #[test]
+    fn bulls_and_cows_keeps_its_own_attempts() {
+        let build = |args: &[&str]| {
+            let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+            Config::build(&args)
+        };
+
+        let config = build(&["guessing_game", "--variant", "bulls", "--max", "200"]).unwrap();
+        assert_eq!(bulls::DEFAULT_ATTEMPTS, config.max_attempts);
+
+        let config = build(&["guessing_game", "--variant", "bulls", "--attempts", "4"]).unwrap();
+        assert_eq!(4, config.max_attempts);
+
+        assert!(build(&["guessing_game", "--variant", "bulls", "--hints"]).is_err());
+        assert!(build(&[
+            "guessing_game",
+            "--variant",
+            "bulls",
+            "--record",
+            "game.txt"
+        ])
+        .is_err());
+    }
+}
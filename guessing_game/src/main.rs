@@ -1,39 +1,29 @@
-use rand::Rng;
-use std::{cmp::Ordering, io};
+use std::{env, process};
 
-fn main() {
-    println!("Guess the number!");
-
-    let secret_number = rand::thread_rng().gen_range(1..=100);
-
-    // println!("The secret number is: {secret_number}");
-    loop {
-        println!("Please input your guess.");
-
-        let mut guess = String::new();
-
-        io::stdin()
-            .read_line(&mut guess)
-            .expect("Failed to read line");
+use guessing_game::{Config, Messages};
 
-        // will crash the program if input was non-numeric value
-        // let guess: u32 = guess.trim().parse().expect("Please enter a number");
-
-        // handling invalid input
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => continue,
-        };
-
-        println!("You guessed: {guess}");
-
-        match guess.cmp(&secret_number) {
-            Ordering::Less => println!("Too Small!"),
-            Ordering::Greater => println!("Too High!"),
-            Ordering::Equal => {
-                println!("You Win!");
-                break;
-            }
-        }
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let messages = Messages::for_lang(&Config::lang(&args));
+
+    let config = Config::build(&args).unwrap_or_else(|err| {
+        let error = messages.get(err);
+        eprintln!(
+            "{}",
+            messages.format("problem-parsing-arguments", &[("error", &error)])
+        );
+        process::exit(1);
+    });
+
+    if let Err(e) = guessing_game::run(config) {
+        // Errors without a message ID, such as those from the OS, are shown
+        // as they are.
+        let e = e.to_string();
+        let error = messages.get(&e);
+        eprintln!(
+            "{}",
+            messages.format("application-error", &[("error", &error)])
+        );
+        process::exit(1);
     }
 }
@@ -0,0 +1,501 @@
+use std::fmt::Display;
+
+/// Everything the player reads, by message ID, in one language. Placeholders
+/// such as `{tries}` are filled in by `Messages::format`.
+type Catalog = &'static [(&'static str, &'static str)];
+
+const EN: Catalog = &[
+    // The number game
+    ("guess-the-number", "Guess the number!"),
+    (
+        "range-and-tries",
+        "It is between {min} and {max}, and you have {tries} tries.",
+    ),
+    (
+        "hint-intro",
+        "Type \"hint\" once to learn if the number is even or odd, for one try.",
+    ),
+    ("input-guess", "Please input your guess."),
+    ("you-guessed", "You guessed: {guess}"),
+    ("too-small", "Too Small!"),
+    ("too-high", "Too High!"),
+    (
+        "repeated",
+        "You already guessed {guess}, so that one doesn't count.",
+    ),
+    ("you-win", "You Win!"),
+    ("you-lose", "You Lose! The secret number was {answer}."),
+    ("you-lose-answer", "You Lose! The answer was {answer}."),
+    ("tries-left", "{tries} tries left."),
+    // Invalid guesses
+    (
+        "not-a-number",
+        "\"{input}\" is not a number, please enter digits only.",
+    ),
+    ("negative", "The secret number is never negative."),
+    (
+        "out-of-range",
+        "Your guess must be between {min} and {max}.",
+    ),
+    // Hints
+    ("burning", "You're burning up!"),
+    ("hot", "Hot."),
+    ("warm", "Warm."),
+    ("cold", "Cold."),
+    ("freezing", "Freezing!"),
+    ("warmer", "Getting warmer."),
+    ("colder", "Getting colder."),
+    ("same-distance", "Just as far as last time."),
+    ("even", "even"),
+    ("odd", "odd"),
+    (
+        "parity-hint",
+        "The secret number is {parity}. {tries} tries left.",
+    ),
+    ("hint-used", "You already used your hint."),
+    (
+        "hint-last-try",
+        "A hint costs a try, and you only have one left.",
+    ),
+    ("game-over", "The game is over."),
+    // Time attack
+    ("time-limit-total", "You have {seconds} seconds to find it."),
+    (
+        "time-limit-per-guess",
+        "You have {seconds} seconds for each guess.",
+    ),
+    ("time-left", "{seconds}s left on the clock."),
+    ("time-up", "Time's up!"),
+    ("elapsed", "Time: {seconds}s"),
+    ("points", "Score: {points} points"),
+    // Bulls and Cows
+    (
+        "bulls-intro",
+        "Guess the {digits}-digit code! No digit appears twice.\n\
+         A bull is a right digit in the right place, a cow is a right digit in the wrong place.\n\
+         You have {tries} tries.",
+    ),
+    (
+        "bulls-length",
+        "Your guess must be exactly {digits} digits.",
+    ),
+    ("bulls-not-a-digit", "\"{input}\" is not a digit."),
+    (
+        "bulls-repeated-digit",
+        "The digit {digit} appears more than once.",
+    ),
+    ("one-bull", "1 bull"),
+    ("bulls", "{bulls} bulls"),
+    ("one-cow", "1 cow"),
+    ("cows", "{cows} cows"),
+    // The solver
+    ("computer-guessed", "Computer guessed: {guess}"),
+    ("computer-out-of-tries", "The computer ran out of tries!"),
+    ("computer-found", "Found it in {guesses} guesses!"),
+    (
+        "think-of-a-number",
+        "Think of a number between {min} and {max}.",
+    ),
+    ("is-it", "Is it {guess}? (higher/lower/yes)"),
+    // The words each answer can be given with, separated by spaces.
+    ("answer-higher", "higher h"),
+    ("answer-lower", "lower l"),
+    ("answer-yes", "yes y"),
+    (
+        "answer-higher-lower-yes",
+        "Please answer higher, lower or yes.",
+    ),
+    (
+        "inconsistent",
+        "That can't be right, the answers contradict each other.",
+    ),
+    ("got-it", "Got it! Your number is {guess}."),
+    ("it-took", "It took {guesses} guesses."),
+    // The high-score table
+    ("no-games-won", "No games won yet."),
+    ("score-not-saved", "Could not save score: {error}"),
+    (
+        "score-line",
+        "  {place}. {player} - {tries} tries in {seconds}s ({min}..={max})",
+    ),
+    (
+        "corrupt-lines",
+        "Skipped {lines} unreadable line(s) in the scores file.",
+    ),
+    // Multiplayer
+    ("listening", "Listening on {addr}"),
+    ("server-winner", "{player} won by guessing {number}!"),
+    // Command-line errors, where `{error}` is itself a message ID or, for
+    // errors that don't have one, the error as it is.
+    (
+        "problem-parsing-arguments",
+        "Problem parsing arguments: {error}",
+    ),
+    ("application-error", "Application error: {error}"),
+    ("option-needs-value", "this option needs a value"),
+    (
+        "difficulty-invalid",
+        "--difficulty must be easy, normal or hard",
+    ),
+    ("min-not-a-number", "--min must be a number"),
+    ("max-not-a-number", "--max must be a number"),
+    ("attempts-not-a-number", "--attempts must be a number"),
+    ("seed-not-a-number", "--seed must be a number"),
+    (
+        "variant-invalid",
+        "--variant must be number or bulls-and-cows",
+    ),
+    (
+        "time-limits-together",
+        "--time and --time-per-guess can't be used together",
+    ),
+    (
+        "time-limit-not-a-number",
+        "a time limit must be a number of seconds",
+    ),
+    ("time-limit-zero", "a time limit must be at least 1 second"),
+    ("unknown-option", "unknown option"),
+    (
+        "time-limit-needs-number",
+        "time limits only work with the number variant",
+    ),
+    (
+        "hints-need-number",
+        "--hints only works with the number variant",
+    ),
+    (
+        "record-needs-number",
+        "--record only works with the number variant",
+    ),
+    ("min-not-below-max", "--min must be smaller than --max"),
+    ("attempts-zero", "--attempts must be at least 1"),
+    ("no-more-guesses", "no more guesses to read"),
+    ("no-more-answers", "no more answers to read"),
+    // Replays
+    (
+        "replay-won",
+        "Replay matches the recording, the game ended with a win.",
+    ),
+    (
+        "replay-lost",
+        "Replay matches the recording, the game ended with a loss.",
+    ),
+    (
+        "replay-unfinished",
+        "Replay matches the recording, which stops before the end.",
+    ),
+];
+
+const ES: Catalog = &[
+    ("guess-the-number", "¡Adivina el número!"),
+    (
+        "range-and-tries",
+        "Está entre {min} y {max}, y tienes {tries} intentos.",
+    ),
+    (
+        "hint-intro",
+        "Escribe \"hint\" una vez para saber si el número es par o impar, a cambio de un intento.",
+    ),
+    ("input-guess", "Introduce tu número."),
+    ("you-guessed", "Has dicho: {guess}"),
+    ("too-small", "¡Demasiado pequeño!"),
+    ("too-high", "¡Demasiado grande!"),
+    ("repeated", "Ya dijiste {guess}, así que no cuenta."),
+    ("you-win", "¡Has ganado!"),
+    ("you-lose", "¡Has perdido! El número secreto era {answer}."),
+    (
+        "you-lose-answer",
+        "¡Has perdido! La respuesta era {answer}.",
+    ),
+    ("tries-left", "Te quedan {tries} intentos."),
+    (
+        "not-a-number",
+        "\"{input}\" no es un número, escribe solo dígitos.",
+    ),
+    ("negative", "El número secreto nunca es negativo."),
+    (
+        "out-of-range",
+        "Tu número tiene que estar entre {min} y {max}.",
+    ),
+    ("burning", "¡Te quemas!"),
+    ("hot", "Caliente."),
+    ("warm", "Templado."),
+    ("cold", "Frío."),
+    ("freezing", "¡Congelado!"),
+    ("warmer", "Cada vez más caliente."),
+    ("colder", "Cada vez más frío."),
+    ("same-distance", "Igual de lejos que antes."),
+    ("even", "par"),
+    ("odd", "impar"),
+    (
+        "parity-hint",
+        "El número secreto es {parity}. Te quedan {tries} intentos.",
+    ),
+    ("hint-used", "Ya usaste tu pista."),
+    (
+        "hint-last-try",
+        "Una pista cuesta un intento, y solo te queda uno.",
+    ),
+    ("game-over", "La partida ha terminado."),
+    (
+        "time-limit-total",
+        "Tienes {seconds} segundos para encontrarlo.",
+    ),
+    (
+        "time-limit-per-guess",
+        "Tienes {seconds} segundos para cada intento.",
+    ),
+    ("time-left", "Quedan {seconds}s en el reloj."),
+    ("time-up", "¡Se acabó el tiempo!"),
+    ("elapsed", "Tiempo: {seconds}s"),
+    ("points", "Puntuación: {points} puntos"),
+    (
+        "bulls-intro",
+        "¡Adivina el código de {digits} cifras! Ninguna cifra se repite.\n\
+         Un toro es una cifra correcta en su sitio, una vaca es una cifra correcta en otro sitio.\n\
+         Tienes {tries} intentos.",
+    ),
+    (
+        "bulls-length",
+        "Tu código tiene que tener exactamente {digits} cifras.",
+    ),
+    ("bulls-not-a-digit", "\"{input}\" no es una cifra."),
+    (
+        "bulls-repeated-digit",
+        "La cifra {digit} aparece más de una vez.",
+    ),
+    ("one-bull", "1 toro"),
+    ("bulls", "{bulls} toros"),
+    ("one-cow", "1 vaca"),
+    ("cows", "{cows} vacas"),
+    ("computer-guessed", "El ordenador dice: {guess}"),
+    (
+        "computer-out-of-tries",
+        "¡Al ordenador se le acabaron los intentos!",
+    ),
+    ("computer-found", "¡Encontrado en {guesses} intentos!"),
+    ("think-of-a-number", "Piensa un número entre {min} y {max}."),
+    ("is-it", "¿Es {guess}? (mayor/menor/sí)"),
+    ("answer-higher", "mayor"),
+    ("answer-lower", "menor"),
+    ("answer-yes", "sí si s"),
+    ("answer-higher-lower-yes", "Responde mayor, menor o sí."),
+    (
+        "inconsistent",
+        "Eso no puede ser, las respuestas se contradicen.",
+    ),
+    ("got-it", "¡Lo tengo! Tu número es {guess}."),
+    ("it-took", "Han hecho falta {guesses} intentos."),
+    ("no-games-won", "Todavía no se ha ganado ninguna partida."),
+    (
+        "score-not-saved",
+        "No se ha podido guardar la puntuación: {error}",
+    ),
+    (
+        "score-line",
+        "  {place}. {player} - {tries} intentos en {seconds}s ({min}..={max})",
+    ),
+    (
+        "corrupt-lines",
+        "Se han saltado {lines} línea(s) ilegibles del fichero de puntuaciones.",
+    ),
+    ("listening", "Escuchando en {addr}"),
+    ("server-winner", "¡{player} ha ganado diciendo {number}!"),
+    (
+        "problem-parsing-arguments",
+        "Problema al leer los argumentos: {error}",
+    ),
+    ("application-error", "Error de la aplicación: {error}"),
+    ("option-needs-value", "esta opción necesita un valor"),
+    (
+        "difficulty-invalid",
+        "--difficulty tiene que ser easy, normal o hard",
+    ),
+    ("min-not-a-number", "--min tiene que ser un número"),
+    ("max-not-a-number", "--max tiene que ser un número"),
+    (
+        "attempts-not-a-number",
+        "--attempts tiene que ser un número",
+    ),
+    ("seed-not-a-number", "--seed tiene que ser un número"),
+    (
+        "variant-invalid",
+        "--variant tiene que ser number o bulls-and-cows",
+    ),
+    (
+        "time-limits-together",
+        "--time y --time-per-guess no se pueden usar juntos",
+    ),
+    (
+        "time-limit-not-a-number",
+        "un límite de tiempo tiene que ser un número de segundos",
+    ),
+    (
+        "time-limit-zero",
+        "un límite de tiempo tiene que ser de al menos 1 segundo",
+    ),
+    ("unknown-option", "opción desconocida"),
+    (
+        "time-limit-needs-number",
+        "los límites de tiempo solo funcionan con la variante number",
+    ),
+    (
+        "hints-need-number",
+        "--hints solo funciona con la variante number",
+    ),
+    (
+        "record-needs-number",
+        "--record solo funciona con la variante number",
+    ),
+    ("min-not-below-max", "--min tiene que ser menor que --max"),
+    ("attempts-zero", "--attempts tiene que ser al menos 1"),
+    ("no-more-guesses", "no quedan más números por leer"),
+    ("no-more-answers", "no quedan más respuestas por leer"),
+    (
+        "replay-won",
+        "La repetición coincide con la grabación, la partida acabó en victoria.",
+    ),
+    (
+        "replay-lost",
+        "La repetición coincide con la grabación, la partida acabó en derrota.",
+    ),
+    (
+        "replay-unfinished",
+        "La repetición coincide con la grabación, que se corta antes del final.",
+    ),
+];
+
+/// The messages of one language, falling back to English for any message ID
+/// the language doesn't have.
+#[derive(Debug, Clone, Copy)]
+pub struct Messages {
+    catalog: Catalog,
+}
+
+impl Messages {
+    pub fn english() -> Messages {
+        Messages { catalog: EN }
+    }
+
+    /// Picks the catalog for a language such as `es` or a locale such as
+    /// `es_ES.UTF-8`, as found in `LANG`. Unknown languages get English.
+    pub fn for_lang(lang: &str) -> Messages {
+        let language = lang.split(['_', '.', '-']).next().unwrap_or_default();
+
+        let catalog = match language.to_lowercase().as_str() {
+            "es" => ES,
+            _ => EN,
+        };
+
+        Messages { catalog }
+    }
+
+    /// The message with this ID. When even English doesn't have it, the ID
+    /// itself is shown so the gap is easy to spot.
+    pub fn get<'a>(&self, id: &'a str) -> &'a str {
+        let find = |catalog: Catalog| {
+            catalog
+                .iter()
+                .find(|(key, _)| *key == id)
+                .map(|(_, text)| *text)
+        };
+
+        find(self.catalog).or_else(|| find(EN)).unwrap_or(id)
+    }
+
+    /// The message with this ID, with each `{name}` replaced by its value.
+    /// The template is filled in a single pass, so a value that contains
+    /// something like `{tries}` is shown as it is.
+    pub fn format(&self, id: &str, args: &[(&str, &dyn Display)]) -> String {
+        let mut text = String::new();
+        let mut rest = self.get(id);
+
+        while let Some(open) = rest.find('{') {
+            text.push_str(&rest[..open]);
+            rest = &rest[open..];
+
+            let placeholder = rest.find('}').and_then(|close| {
+                let (_, value) = args.iter().find(|(name, _)| *name == &rest[1..close])?;
+                Some((close, value))
+            });
+
+            match placeholder {
+                Some((close, value)) => {
+                    text.push_str(&value.to_string());
+                    rest = &rest[close + 1..];
+                }
+                // A brace that doesn't start a known placeholder is kept.
+                None => {
+                    text.push('{');
+                    rest = &rest[1..];
+                }
+            }
+        }
+
+        text.push_str(rest);
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_language_from_locale() {
+        assert_eq!(
+            "¡Has ganado!",
+            Messages::for_lang("es_ES.UTF-8").get("you-win")
+        );
+        assert_eq!("You Win!", Messages::for_lang("C.UTF-8").get("you-win"));
+        assert_eq!("You Win!", Messages::for_lang("").get("you-win"));
+    }
+
+    #[test]
+    fn falls_back_to_english_then_to_the_id() {
+        let partial = Messages {
+            catalog: &[("you-win", "¡Has ganado!")],
+        };
+
+        assert_eq!("¡Has ganado!", partial.get("you-win"));
+        assert_eq!("Too High!", partial.get("too-high"));
+        assert_eq!("no-such-message", partial.get("no-such-message"));
+    }
+
+    #[test]
+    fn fills_in_placeholders() {
+        let messages = Messages::english();
+
+        assert_eq!(
+            "It is between 1 and 100, and you have 7 tries.",
+            messages.format(
+                "range-and-tries",
+                &[("min", &1), ("max", &100), ("tries", &7)]
+            )
+        );
+    }
+
+    #[test]
+    fn values_are_not_filled_in_again() {
+        let line = Messages::english().format(
+            "score-line",
+            &[
+                ("place", &1),
+                ("player", &"{tries}"),
+                ("tries", &3),
+                ("seconds", &"4.0"),
+                ("min", &1),
+                ("max", &100),
+            ],
+        );
+
+        assert_eq!("  1. {tries} - 3 tries in 4.0s (1..=100)", line);
+    }
+
+    #[test]
+    fn every_spanish_message_exists_in_english() {
+        for (id, _) in ES {
+            assert!(EN.iter().any(|(key, _)| key == id), "{id}");
+        }
+    }
+}
@@ -0,0 +1,410 @@
+use std::{
+    error::Error,
+    fs,
+    io::{self, BufRead, Read, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use crate::{play_engine, Config, Game, Messages, Outcome, State, TimeLimit};
+
+/// Wraps the player's input and keeps a copy of every line read from it,
+/// with the time since the game started, and when it timed out if it did.
+pub struct Recorder<R> {
+    inner: R,
+    started: Instant,
+    pending: Vec<u8>,
+    lines: Vec<(Duration, String)>,
+    timed_out: Option<Duration>,
+}
+
+// Recordings are saved in whole milliseconds.
+fn millis_since(started: Instant) -> Duration {
+    Duration::from_millis(started.elapsed().as_millis() as u64)
+}
+
+impl<R: BufRead> Recorder<R> {
+    pub fn new(inner: R) -> Recorder<R> {
+        Recorder {
+            inner,
+            started: Instant::now(),
+            pending: Vec::new(),
+            lines: Vec::new(),
+            timed_out: None,
+        }
+    }
+
+    /// When reading from the input timed out, if it did.
+    pub fn timed_out(&self) -> Option<Duration> {
+        self.timed_out
+    }
+
+    /// The recorded lines, including a last one that had no newline.
+    pub fn into_lines(mut self) -> Vec<(Duration, String)> {
+        if !self.pending.is_empty() {
+            let last = std::mem::take(&mut self.pending);
+            self.push_line(&last);
+        }
+
+        self.lines
+    }
+
+    fn push_line(&mut self, line: &[u8]) {
+        let line = String::from_utf8_lossy(line);
+        let line = line.trim_end_matches(['\r', '\n']).to_string();
+
+        self.lines.push((millis_since(self.started), line));
+    }
+}
+
+impl<R: BufRead> Read for Recorder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for Recorder<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let started = self.started;
+        let timed_out = &mut self.timed_out;
+
+        self.inner.fill_buf().inspect_err(|e| {
+            if e.kind() == io::ErrorKind::TimedOut {
+                timed_out.get_or_insert_with(|| millis_since(started));
+            }
+        })
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // The bytes being consumed are still at the front of the inner
+        // buffer, so asking for it again doesn't read anything new.
+        if let Ok(buf) = self.inner.fill_buf() {
+            self.pending.extend_from_slice(&buf[..amt]);
+        }
+        self.inner.consume(amt);
+
+        while let Some(end) = self.pending.iter().position(|&byte| byte == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=end).collect();
+            self.push_line(&line);
+        }
+    }
+}
+
+/// Everything needed to play a game again: the settings it was played with,
+/// what the player typed and what each guess led to.
+#[derive(Debug, PartialEq)]
+pub struct Recording {
+    pub seed: u64,
+    pub min: u32,
+    pub max: u32,
+    pub max_attempts: u32,
+    pub hints: bool,
+    pub time_limit: Option<TimeLimit>,
+    pub inputs: Vec<(Duration, String)>,
+    /// When the player ran out of time, if they did.
+    pub timed_out: Option<Duration>,
+    pub guesses: Vec<(u32, Outcome)>,
+}
+
+/// Input that has run out because the clock did, for replaying a game that
+/// was lost on time.
+struct TimesOut;
+
+impl Read for TimesOut {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::new(io::ErrorKind::TimedOut, "time was up"))
+    }
+}
+
+fn outcome_name(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::TooSmall => "too-small",
+        Outcome::TooHigh => "too-high",
+        Outcome::Win => "win",
+        Outcome::Lose => "lose",
+        Outcome::Repeated => "repeated",
+    }
+}
+
+fn parse_outcome(name: &str) -> Option<Outcome> {
+    let outcome = match name {
+        "too-small" => Outcome::TooSmall,
+        "too-high" => Outcome::TooHigh,
+        "win" => Outcome::Win,
+        "lose" => Outcome::Lose,
+        "repeated" => Outcome::Repeated,
+        _ => return None,
+    };
+
+    Some(outcome)
+}
+
+impl Recording {
+    pub fn new<R: BufRead>(game: &Game, config: &Config, recorder: Recorder<R>) -> Recording {
+        Recording {
+            seed: game.seed(),
+            min: *config.range.start(),
+            max: *config.range.end(),
+            max_attempts: config.max_attempts,
+            hints: config.hints,
+            time_limit: config.time_limit,
+            timed_out: recorder.timed_out(),
+            inputs: recorder.into_lines(),
+            guesses: game.history().to_vec(),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut file = fs::File::create(path)?;
+
+        writeln!(file, "seed {}", self.seed)?;
+        writeln!(file, "range {} {}", self.min, self.max)?;
+        writeln!(file, "attempts {}", self.max_attempts)?;
+        writeln!(file, "hints {}", self.hints)?;
+        match self.time_limit {
+            Some(TimeLimit::Total(limit)) => writeln!(file, "time total {}", limit.as_millis())?,
+            Some(TimeLimit::PerGuess(limit)) => {
+                writeln!(file, "time per-guess {}", limit.as_millis())?
+            }
+            None => {}
+        }
+
+        for (at, line) in &self.inputs {
+            writeln!(file, "input {} {line}", at.as_millis())?;
+        }
+        if let Some(at) = self.timed_out {
+            writeln!(file, "timeout {}", at.as_millis())?;
+        }
+        for (guess, outcome) in &self.guesses {
+            writeln!(file, "guess {guess} {}", outcome_name(*outcome))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Recording, Box<dyn Error>> {
+        let contents = fs::read_to_string(path)?;
+        let mut recording = Recording {
+            seed: 0,
+            min: 0,
+            max: 0,
+            max_attempts: 0,
+            hints: false,
+            time_limit: None,
+            inputs: Vec::new(),
+            timed_out: None,
+            guesses: Vec::new(),
+        };
+
+        for (number, line) in contents.lines().enumerate() {
+            let bad_line = || format!("line {} of the recording is not valid", number + 1);
+            let (key, rest) = line.split_once(' ').ok_or_else(bad_line)?;
+
+            match key {
+                "seed" => recording.seed = rest.parse().map_err(|_| bad_line())?,
+                "range" => {
+                    let (min, max) = rest.split_once(' ').ok_or_else(bad_line)?;
+                    recording.min = min.parse().map_err(|_| bad_line())?;
+                    recording.max = max.parse().map_err(|_| bad_line())?;
+                }
+                "attempts" => recording.max_attempts = rest.parse().map_err(|_| bad_line())?,
+                "hints" => recording.hints = rest.parse().map_err(|_| bad_line())?,
+                "time" => {
+                    let (kind, millis) = rest.split_once(' ').ok_or_else(bad_line)?;
+                    let limit = Duration::from_millis(millis.parse().map_err(|_| bad_line())?);
+                    recording.time_limit = Some(match kind {
+                        "total" => TimeLimit::Total(limit),
+                        "per-guess" => TimeLimit::PerGuess(limit),
+                        _ => return Err(bad_line().into()),
+                    });
+                }
+                "input" => {
+                    // The typed line may be empty, so there is no second space.
+                    let (millis, input) = rest.split_once(' ').unwrap_or((rest, ""));
+                    let at = Duration::from_millis(millis.parse().map_err(|_| bad_line())?);
+                    recording.inputs.push((at, input.to_string()));
+                }
+                "timeout" => {
+                    let at = Duration::from_millis(rest.parse().map_err(|_| bad_line())?);
+                    recording.timed_out = Some(at);
+                }
+                "guess" => {
+                    let (guess, outcome) = rest.split_once(' ').ok_or_else(bad_line)?;
+                    let guess = guess.parse().map_err(|_| bad_line())?;
+                    let outcome = parse_outcome(outcome).ok_or_else(bad_line)?;
+                    recording.guesses.push((guess, outcome));
+                }
+                _ => return Err(bad_line().into()),
+            }
+        }
+
+        if recording.min >= recording.max || recording.max_attempts == 0 {
+            return Err("the recording has no valid range or attempts".into());
+        }
+
+        Ok(recording)
+    }
+
+    /// Plays the recorded input again with the recorded seed and settings,
+    /// showing the game on `output`, and checks every guess ends the same way.
+    /// A game that was lost on time runs out of time again after the last
+    /// recorded line. Returns how the game ended, or `None` if the recording
+    /// stops early.
+    pub fn replay(
+        &self,
+        lang: &str,
+        mut output: impl Write,
+    ) -> Result<Option<State>, Box<dyn Error>> {
+        let config = Config {
+            range: self.min..=self.max,
+            max_attempts: self.max_attempts,
+            hints: self.hints,
+            time_limit: self.time_limit,
+            lang: lang.to_string(),
+            ..Config::preset("normal").unwrap()
+        };
+        let mut game = Game::with_seed(&config, self.seed);
+
+        let input: String = self
+            .inputs
+            .iter()
+            .map(|(_, line)| format!("{line}\n"))
+            .collect();
+
+        let input: Box<dyn BufRead> = match self.timed_out {
+            Some(_) => Box::new(io::BufReader::new(input.as_bytes().chain(TimesOut))),
+            None => Box::new(input.as_bytes()),
+        };
+
+        // A recording of a game that was quit early runs out of input too.
+        let messages = Messages::for_lang(lang);
+        let state = match play_engine(&mut game, &messages, self.time_limit, input, &mut output) {
+            Ok(state) => Some(state),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => None,
+            Err(e) => return Err(e.into()),
+        };
+
+        let replayed = game.history();
+        for (i, recorded) in self.guesses.iter().enumerate() {
+            match replayed.get(i) {
+                Some(replayed) if replayed == recorded => {}
+                Some(replayed) => {
+                    return Err(format!(
+                        "guess {} differs: recorded {:?}, replayed {:?}",
+                        i + 1,
+                        recorded,
+                        replayed
+                    )
+                    .into())
+                }
+                None => return Err(format!("guess {} was not replayed", i + 1).into()),
+            }
+        }
+        if replayed.len() > self.guesses.len() {
+            return Err("the replay made more guesses than the recording".into());
+        }
+
+        Ok(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(config: &Config, input: &str) -> Recording {
+        let mut game = Game::with_seed(config, 99);
+        let mut recorder = Recorder::new(input.as_bytes());
+
+        play_engine(
+            &mut game,
+            &Messages::english(),
+            None,
+            &mut recorder,
+            io::sink(),
+        )
+        .unwrap();
+
+        Recording::new(&game, config, recorder)
+    }
+
+    #[test]
+    fn recorder_keeps_every_line() {
+        let mut recorder = Recorder::new("50\nabc\n\n7".as_bytes());
+        let mut all = String::new();
+        recorder.read_to_string(&mut all).unwrap();
+
+        let lines: Vec<String> = recorder.into_lines().into_iter().map(|(_, l)| l).collect();
+
+        assert_eq!("50\nabc\n\n7", all);
+        assert_eq!(vec!["50", "abc", "", "7"], lines);
+    }
+
+    #[test]
+    fn replay_of_a_saved_recording_matches() {
+        let config = Config::preset("normal").unwrap();
+        let secret_number = Game::with_seed(&config, 99).secret_number();
+        let input = format!("oops\n{}\n{secret_number}\n", secret_number ^ 1);
+        let recording = record(&config, &input);
+        let path = std::env::temp_dir().join(format!("recording-{}.txt", std::process::id()));
+
+        recording.save(&path).unwrap();
+        let loaded = Recording::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(recording, loaded);
+        assert_eq!(Some(State::Won), loaded.replay("en", io::sink()).unwrap());
+    }
+
+    #[test]
+    fn replay_reports_a_different_outcome() {
+        let config = Config::preset("normal").unwrap();
+        let secret_number = Game::with_seed(&config, 99).secret_number();
+        let mut recording = record(&config, &format!("{secret_number}\n"));
+
+        recording.guesses[0].1 = Outcome::TooHigh;
+
+        let error = recording.replay("en", io::sink()).unwrap_err();
+        assert!(error.to_string().starts_with("guess 1 differs"));
+    }
+
+    #[test]
+    fn replay_runs_out_of_time_where_the_recording_did() {
+        let config = Config {
+            time_limit: Some(TimeLimit::PerGuess(Duration::from_secs(5))),
+            ..Config::preset("normal").unwrap()
+        };
+        let secret_number = Game::with_seed(&config, 99).secret_number();
+        let input = format!("{}\n", secret_number ^ 1);
+
+        let mut game = Game::with_seed(&config, 99);
+        let mut recorder = Recorder::new(io::BufReader::new(input.as_bytes().chain(TimesOut)));
+        let state = play_engine(
+            &mut game,
+            &Messages::english(),
+            config.time_limit,
+            &mut recorder,
+            io::sink(),
+        )
+        .unwrap();
+        let recording = Recording::new(&game, &config, recorder);
+        let path = std::env::temp_dir().join(format!("timeout-{}.txt", std::process::id()));
+
+        recording.save(&path).unwrap();
+        let loaded = Recording::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(State::Lost, state);
+        assert!(loaded.timed_out.is_some());
+        assert_eq!(recording, loaded);
+
+        let mut output = Vec::new();
+        assert_eq!(Some(State::Lost), loaded.replay("en", &mut output).unwrap());
+        assert!(String::from_utf8(output).unwrap().contains("Time's up!"));
+    }
+}
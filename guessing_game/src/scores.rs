@@ -0,0 +1,227 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    ops::RangeInclusive,
+    path::Path,
+    str::FromStr,
+    time::Duration,
+};
+
+use crate::Messages;
+
+pub const DEFAULT_PATH: &str = "guessing_game_scores.txt";
+
+/// How many results `ScoreTable::render` shows for each difficulty.
+const SHOWN_PER_DIFFICULTY: usize = 5;
+
+/// One finished game. It is stored as a single tab-separated line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Score {
+    pub player: String,
+    pub difficulty: String,
+    pub range: RangeInclusive<u32>,
+    pub attempts: u32,
+    pub elapsed: Duration,
+    pub won: bool,
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Tabs and newlines in a name would break the line format.
+        let player = self.player.replace(['\t', '\n', '\r'], " ");
+
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            player,
+            self.difficulty,
+            self.range.start(),
+            self.range.end(),
+            self.attempts,
+            self.elapsed.as_millis(),
+            if self.won { "won" } else { "lost" }
+        )
+    }
+}
+
+impl FromStr for Score {
+    type Err = &'static str;
+
+    fn from_str(line: &str) -> Result<Score, Self::Err> {
+        let fields: Vec<&str> = line.split('\t').collect();
+
+        let [player, difficulty, min, max, attempts, millis, result] = fields[..] else {
+            return Err("expected 7 tab-separated fields");
+        };
+
+        let number = |field: &str| field.parse::<u32>().map_err(|_| "expected a number");
+
+        let won = match result {
+            "won" => true,
+            "lost" => false,
+            _ => return Err("expected won or lost"),
+        };
+
+        Ok(Score {
+            player: player.to_string(),
+            difficulty: difficulty.to_string(),
+            range: number(min)?..=number(max)?,
+            attempts: number(attempts)?,
+            elapsed: Duration::from_millis(millis.parse().map_err(|_| "expected a number")?),
+            won,
+        })
+    }
+}
+
+/// Every score in the scores file, and how many lines of it were unreadable.
+#[derive(Debug, Default)]
+pub struct ScoreTable {
+    pub scores: Vec<Score>,
+    pub corrupt_lines: usize,
+}
+
+/// Reads the scores file. A missing file is an empty table, and lines that
+/// can't be parsed are counted and skipped so one bad write doesn't lose the
+/// whole history.
+pub fn load(path: &Path) -> io::Result<ScoreTable> {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(ScoreTable::default()),
+        Err(e) => return Err(e),
+    };
+
+    let mut table = ScoreTable::default();
+
+    for line in String::from_utf8_lossy(&bytes).lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match line.parse() {
+            Ok(score) => table.scores.push(score),
+            Err(_) => table.corrupt_lines += 1,
+        }
+    }
+
+    Ok(table)
+}
+
+/// Adds `score` to the end of the scores file, creating it if needed.
+pub fn append(path: &Path, score: &Score) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+    writeln!(file, "{score}")
+}
+
+impl ScoreTable {
+    /// The won games of each difficulty, fewest attempts first and the
+    /// quickest first among equal attempts.
+    pub fn best_per_difficulty(&self) -> BTreeMap<&str, Vec<&Score>> {
+        let mut best: BTreeMap<&str, Vec<&Score>> = BTreeMap::new();
+
+        for score in self.scores.iter().filter(|score| score.won) {
+            best.entry(&score.difficulty).or_default().push(score);
+        }
+
+        for scores in best.values_mut() {
+            scores.sort_by_key(|score| (score.attempts, score.elapsed));
+        }
+
+        best
+    }
+
+    pub fn render(&self, messages: &Messages, mut output: impl Write) -> io::Result<()> {
+        let best = self.best_per_difficulty();
+
+        if best.is_empty() {
+            writeln!(output, "{}", messages.get("no-games-won"))?;
+        }
+
+        for (difficulty, scores) in best {
+            writeln!(output, "{difficulty}:")?;
+
+            for (place, score) in scores.iter().take(SHOWN_PER_DIFFICULTY).enumerate() {
+                let line = messages.format(
+                    "score-line",
+                    &[
+                        ("place", &(place + 1)),
+                        ("player", &score.player),
+                        ("tries", &score.attempts),
+                        ("seconds", &format!("{:.1}", score.elapsed.as_secs_f64())),
+                        ("min", score.range.start()),
+                        ("max", score.range.end()),
+                    ],
+                );
+                writeln!(output, "{line}")?;
+            }
+        }
+
+        if self.corrupt_lines > 0 {
+            writeln!(
+                output,
+                "{}",
+                messages.format("corrupt-lines", &[("lines", &self.corrupt_lines)])
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(player: &str, difficulty: &str, attempts: u32, won: bool) -> Score {
+        Score {
+            player: player.to_string(),
+            difficulty: difficulty.to_string(),
+            range: 1..=100,
+            attempts,
+            elapsed: Duration::from_millis(1500),
+            won,
+        }
+    }
+
+    #[test]
+    fn score_round_trips_through_a_line() {
+        let score = score("ferris", "normal", 4, true);
+
+        assert_eq!(Ok(score.clone()), score.to_string().parse());
+    }
+
+    #[test]
+    fn best_scores_skip_losses_and_sort_by_attempts() {
+        let table = ScoreTable {
+            scores: vec![
+                score("a", "normal", 6, true),
+                score("b", "normal", 3, true),
+                score("c", "normal", 1, false),
+                score("d", "hard", 9, true),
+            ],
+            corrupt_lines: 0,
+        };
+
+        let best = table.best_per_difficulty();
+        let normal: Vec<&str> = best["normal"].iter().map(|s| s.player.as_str()).collect();
+
+        assert_eq!(vec!["b", "a"], normal);
+        assert_eq!(1, best["hard"].len());
+    }
+
+    #[test]
+    fn corrupt_lines_are_skipped_and_counted() {
+        let path = std::env::temp_dir().join(format!("scores-{}.txt", std::process::id()));
+        let good = score("ferris", "easy", 2, true);
+        fs::write(&path, b"garbage\n\xff\xfe\nfoo\tbar\n").unwrap();
+        append(&path, &good).unwrap();
+
+        let table = load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(vec![good], table.scores);
+        assert_eq!(3, table.corrupt_lines);
+    }
+}
@@ -0,0 +1,267 @@
+// A line-based protocol for racing to guess one shared secret number:
+//
+//   server: WELCOME <min> <max>
+//   client: NAME <name>          (optional, defaults to "player <n>")
+//   server: OK
+//   client: <number>
+//   server: LOW | HIGH | ERROR <reason>
+//
+// When someone guesses right, every client gets `WINNER <name> <number>` and
+// the server closes all connections.
+
+use std::{
+    cmp::Ordering,
+    io::{self, BufRead, BufReader, Write},
+    net::{Shutdown, TcpListener, TcpStream, ToSocketAddrs},
+    ops::RangeInclusive,
+    sync::{
+        mpsc::{self, Sender, TryRecvError},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use crate::{Config, Game};
+
+pub const DEFAULT_ADDR: &str = "127.0.0.1:7878";
+
+/// How long the accept loop sleeps between checks for a winner.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The player who guessed the number first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Winner {
+    pub name: String,
+    pub number: u32,
+}
+
+struct Shared {
+    /// The players still connected, by number, so the winner can be announced
+    /// to all of them.
+    clients: Vec<(usize, TcpStream)>,
+    winner: Option<Winner>,
+}
+
+pub struct Server {
+    listener: TcpListener,
+    range: RangeInclusive<u32>,
+    secret_number: u32,
+}
+
+impl Server {
+    /// Listens on `addr` with a random secret number in the config's range.
+    pub fn bind(addr: impl ToSocketAddrs, config: &Config) -> io::Result<Server> {
+        let secret_number = Game::new(config).secret_number();
+
+        Ok(Server::new(
+            TcpListener::bind(addr)?,
+            config.range.clone(),
+            secret_number,
+        ))
+    }
+
+    pub fn new(listener: TcpListener, range: RangeInclusive<u32>, secret_number: u32) -> Server {
+        Server {
+            listener,
+            range,
+            secret_number,
+        }
+    }
+
+    pub fn listener(&self) -> &TcpListener {
+        &self.listener
+    }
+
+    /// Accepts players until one of them guesses the number, then tells
+    /// everyone who won and disconnects them.
+    pub fn run(self) -> io::Result<Winner> {
+        let shared = Arc::new(Mutex::new(Shared {
+            clients: Vec::new(),
+            winner: None,
+        }));
+        let (winner_tx, winner_rx) = mpsc::channel();
+
+        // Accepting without blocking lets the loop notice the winner even if
+        // nobody else connects.
+        self.listener.set_nonblocking(true)?;
+        let mut players = 0;
+
+        let winner = loop {
+            match winner_rx.try_recv() {
+                Ok(winner) => break winner,
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => unreachable!("the server keeps a sender"),
+            }
+
+            match self.listener.accept() {
+                Ok((stream, _)) => {
+                    players += 1;
+                    stream.set_nonblocking(false)?;
+                    let clone = stream.try_clone()?;
+                    shared.lock().unwrap().clients.push((players, clone));
+
+                    let client = Client {
+                        id: players,
+                        name: format!("player {players}"),
+                        range: self.range.clone(),
+                        secret_number: self.secret_number,
+                        shared: Arc::clone(&shared),
+                        winner_tx: winner_tx.clone(),
+                    };
+                    thread::spawn(move || client.handle(stream));
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => thread::sleep(POLL_INTERVAL),
+                Err(e) => return Err(e),
+            }
+        };
+
+        for (_, client) in &shared.lock().unwrap().clients {
+            let _ = client.shutdown(Shutdown::Both);
+        }
+
+        Ok(winner)
+    }
+}
+
+struct Client {
+    id: usize,
+    name: String,
+    range: RangeInclusive<u32>,
+    secret_number: u32,
+    shared: Arc<Mutex<Shared>>,
+    winner_tx: Sender<Winner>,
+}
+
+impl Client {
+    fn handle(mut self, stream: TcpStream) {
+        // A client that hangs up only ends its own connection, and is no
+        // longer sent the winner.
+        let _ = self.talk(&stream);
+
+        let mut shared = self.shared.lock().unwrap();
+        shared.clients.retain(|(id, _)| *id != self.id);
+    }
+
+    // Every write to a client happens under the lock and as a single call, so
+    // a reply and the winner broadcast can never be mixed in one line.
+    fn send(&self, mut stream: &TcpStream, line: &str) -> io::Result<()> {
+        let _shared = self.shared.lock().unwrap();
+        stream.write_all(format!("{line}\n").as_bytes())
+    }
+
+    fn talk(&mut self, stream: &TcpStream) -> io::Result<()> {
+        let welcome = format!("WELCOME {} {}", self.range.start(), self.range.end());
+        self.send(stream, &welcome)?;
+
+        for line in BufReader::new(stream).lines() {
+            let line = line?;
+
+            if let Some(name) = line.strip_prefix("NAME ") {
+                self.name = name.trim().to_string();
+                self.send(stream, "OK")?;
+                continue;
+            }
+
+            let guess: u32 = match line.trim().parse() {
+                Ok(num) => num,
+                Err(_) => {
+                    self.send(stream, "ERROR expected a number")?;
+                    continue;
+                }
+            };
+
+            match guess.cmp(&self.secret_number) {
+                Ordering::Less => self.send(stream, "LOW")?,
+                Ordering::Greater => self.send(stream, "HIGH")?,
+                Ordering::Equal => {
+                    self.win(guess);
+                    return Ok(());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn win(&self, number: u32) {
+        let mut shared = self.shared.lock().unwrap();
+
+        // Two right guesses can arrive at once; only the first one counts.
+        if shared.winner.is_some() {
+            return;
+        }
+
+        let winner = Winner {
+            name: self.name.clone(),
+            number,
+        };
+
+        let announcement = format!("WINNER {} {}\n", winner.name, winner.number);
+        for mut client in shared.clients.iter().map(|(_, client)| client) {
+            let _ = client.write_all(announcement.as_bytes());
+        }
+
+        shared.winner = Some(winner.clone());
+        let _ = self.winner_tx.send(winner);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connect(addr: std::net::SocketAddr) -> (BufReader<TcpStream>, TcpStream) {
+        let stream = TcpStream::connect(addr).unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+        assert_eq!("WELCOME 1 100\n", read_line(&mut reader));
+
+        (reader, stream)
+    }
+
+    fn read_line(reader: &mut impl BufRead) -> String {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        line
+    }
+
+    #[test]
+    fn first_right_guess_wins_and_everyone_is_told() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = thread::spawn(move || Server::new(listener, 1..=100, 42).run());
+
+        let (mut alice_in, mut alice) = connect(addr);
+        let (mut bob_in, mut bob) = connect(addr);
+        let (mut carol_in, mut carol) = connect(addr);
+
+        writeln!(alice, "NAME alice").unwrap();
+        assert_eq!("OK\n", read_line(&mut alice_in));
+        writeln!(bob, "NAME bob").unwrap();
+        assert_eq!("OK\n", read_line(&mut bob_in));
+
+        writeln!(alice, "10").unwrap();
+        assert_eq!("LOW\n", read_line(&mut alice_in));
+        writeln!(carol, "ninety").unwrap();
+        assert_eq!("ERROR expected a number\n", read_line(&mut carol_in));
+        writeln!(carol, "90").unwrap();
+        assert_eq!("HIGH\n", read_line(&mut carol_in));
+
+        writeln!(bob, "42").unwrap();
+
+        let winner = server.join().unwrap().unwrap();
+        assert_eq!(
+            Winner {
+                name: String::from("bob"),
+                number: 42
+            },
+            winner
+        );
+
+        for reader in [&mut alice_in, &mut bob_in, &mut carol_in] {
+            assert_eq!("WINNER bob 42\n", read_line(reader));
+            assert_eq!("", read_line(reader));
+        }
+    }
+}
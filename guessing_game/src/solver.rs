@@ -0,0 +1,253 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    io::{self, BufRead, Write},
+    ops::RangeInclusive,
+};
+
+use crate::{Game, Messages, Outcome};
+
+/// The answers given so far rule out every number in the range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Inconsistent;
+
+impl fmt::Display for Inconsistent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the answers contradict each other")
+    }
+}
+
+impl std::error::Error for Inconsistent {}
+
+/// Plays the guesser's side with a binary search, which never needs more than
+/// log2 of the range size plus one guess.
+#[derive(Debug)]
+pub struct Solver {
+    low: u32,
+    high: u32,
+    guesses: u32,
+}
+
+impl Solver {
+    pub fn new(range: &RangeInclusive<u32>) -> Solver {
+        Solver {
+            low: *range.start(),
+            high: *range.end(),
+            guesses: 0,
+        }
+    }
+
+    /// The middle of the numbers that are still possible.
+    pub fn next_guess(&self) -> u32 {
+        self.low + (self.high - self.low) / 2
+    }
+
+    pub fn guesses(&self) -> u32 {
+        self.guesses
+    }
+
+    /// Narrows the range down with how `guess` compared to the secret number,
+    /// the same `guess.cmp(&secret_number)` the game itself uses.
+    pub fn answer(&mut self, guess: u32, ordering: Ordering) -> Result<(), Inconsistent> {
+        self.guesses += 1;
+
+        match ordering {
+            Ordering::Less if guess < self.high => self.low = guess + 1,
+            Ordering::Greater if guess > self.low => self.high = guess - 1,
+            Ordering::Equal if (self.low..=self.high).contains(&guess) => {
+                self.low = guess;
+                self.high = guess;
+            }
+            _ => return Err(Inconsistent),
+        }
+
+        Ok(())
+    }
+}
+
+/// Lets the solver play `game` and prints each guess to `output`.
+pub fn solve(game: &mut Game, messages: &Messages, mut output: impl Write) -> io::Result<Outcome> {
+    let mut solver = Solver::new(game.range());
+
+    loop {
+        let guess = solver.next_guess();
+        writeln!(
+            output,
+            "{}",
+            messages.format("computer-guessed", &[("guess", &guess)])
+        )?;
+
+        let outcome = game.guess(guess);
+        let ordering = match outcome {
+            Outcome::TooSmall => Ordering::Less,
+            Outcome::TooHigh => Ordering::Greater,
+            Outcome::Win => Ordering::Equal,
+            Outcome::Lose => {
+                writeln!(output, "{}", messages.get("computer-out-of-tries"))?;
+                return Ok(outcome);
+            }
+            // The solver never guesses a number it has already ruled out.
+            Outcome::Repeated => unreachable!("binary search repeated {guess}"),
+        };
+
+        if solver.answer(guess, ordering).is_err() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, Inconsistent));
+        }
+
+        if ordering == Ordering::Equal {
+            let guesses = solver.guesses();
+            writeln!(
+                output,
+                "{}",
+                messages.format("computer-found", &[("guesses", &guesses)])
+            )?;
+            return Ok(outcome);
+        }
+    }
+}
+
+/// Guesses a number the player is thinking of. The player answers each guess
+/// with `higher`, `lower` or `yes` (or just `h`, `l` or `y`), or the words the
+/// catalog gives for them, such as `mayor`, `menor` or `sí`. Returns the
+/// number, or `Inconsistent` if the answers can't all be true.
+pub fn solve_human(
+    range: &RangeInclusive<u32>,
+    messages: &Messages,
+    mut input: impl BufRead,
+    mut output: impl Write,
+) -> Result<u32, Box<dyn std::error::Error>> {
+    let mut solver = Solver::new(range);
+
+    writeln!(
+        output,
+        "{}",
+        messages.format(
+            "think-of-a-number",
+            &[("min", range.start()), ("max", range.end())]
+        )
+    )?;
+
+    loop {
+        let guess = solver.next_guess();
+        writeln!(output, "{}", messages.format("is-it", &[("guess", &guess)]))?;
+
+        let mut answer = String::new();
+        if input.read_line(&mut answer)? == 0 {
+            return Err("no-more-answers".into());
+        }
+
+        // The answer says where the secret number is, so "higher" means the
+        // guess compared as less than it.
+        let answer = answer.trim().to_lowercase();
+        let ordering = if is_answer(messages, "answer-higher", &answer) {
+            Ordering::Less
+        } else if is_answer(messages, "answer-lower", &answer) {
+            Ordering::Greater
+        } else if is_answer(messages, "answer-yes", &answer) {
+            Ordering::Equal
+        } else {
+            writeln!(output, "{}", messages.get("answer-higher-lower-yes"))?;
+            continue;
+        };
+
+        if let Err(e) = solver.answer(guess, ordering) {
+            writeln!(output, "{}", messages.get("inconsistent"))?;
+            return Err(e.into());
+        }
+
+        if ordering == Ordering::Equal {
+            let guesses = solver.guesses();
+            writeln!(
+                output,
+                "{}",
+                messages.format("got-it", &[("guess", &guess)])
+            )?;
+            writeln!(
+                output,
+                "{}",
+                messages.format("it-took", &[("guesses", &guesses)])
+            )?;
+            return Ok(guess);
+        }
+    }
+}
+
+// Whether `answer` is one of the words for the answer with this message ID,
+// in the player's language or in English.
+fn is_answer(messages: &Messages, id: &str, answer: &str) -> bool {
+    [messages.get(id), Messages::english().get(id)]
+        .iter()
+        .any(|words| words.split(' ').any(|word| word == answer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Config;
+
+    #[test]
+    fn solves_every_preset_within_its_attempts() {
+        for difficulty in ["easy", "normal", "hard"] {
+            let config = Config::preset(difficulty).unwrap();
+
+            for seed in 0..50 {
+                let mut game = Game::with_seed(&config, seed);
+
+                assert_eq!(
+                    Outcome::Win,
+                    solve(&mut game, &Messages::english(), io::sink()).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn finds_the_players_number() {
+        let mut output = Vec::new();
+
+        let number = solve_human(
+            &(1..=100),
+            &Messages::english(),
+            "h\nl\nmaybe\nh\nyes\n".as_bytes(),
+            &mut output,
+        );
+
+        assert_eq!(68, number.unwrap());
+        assert!(String::from_utf8(output)
+            .unwrap()
+            .contains("It took 4 guesses."));
+    }
+
+    #[test]
+    fn understands_answers_in_the_players_language() {
+        let mut output = Vec::new();
+
+        let number = solve_human(
+            &(1..=100),
+            &Messages::for_lang("es"),
+            "mayor\nmenor\nquizá\nmayor\nSí\n".as_bytes(),
+            &mut output,
+        );
+
+        assert_eq!(68, number.unwrap());
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("¿Es 50? (mayor/menor/sí)"));
+        assert!(output.contains("Responde mayor, menor o sí."));
+    }
+
+    #[test]
+    fn detects_inconsistent_answers() {
+        // 50 is too small, then 75, 62, 56 and 53 are too big, so the number
+        // must be 51 or 52, and then 51 is said to be too big as well.
+        let answers = "h\nl\nl\nl\nl\nl\n";
+
+        let result = solve_human(
+            &(1..=100),
+            &Messages::english(),
+            answers.as_bytes(),
+            io::sink(),
+        );
+
+        assert!(result.unwrap_err().is::<Inconsistent>());
+    }
+}
@@ -0,0 +1,214 @@
+use std::{
+    io::{self, BufRead, Read},
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Points for winning a time-attack game, before the clock takes its share.
+const WIN_POINTS: u32 = 1000;
+
+/// Points lost for every whole second the game took.
+const POINTS_PER_SECOND: u32 = 10;
+
+/// A win is worth at least this much, however slow it was.
+const MIN_WIN_POINTS: u32 = 100;
+
+/// Points for every try that was left over.
+const POINTS_PER_TRY_LEFT: u32 = 50;
+
+/// How long the player has in a time-attack game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeLimit {
+    /// The whole game has to be won within this time.
+    Total(Duration),
+    /// Each guess has to be typed within this time.
+    PerGuess(Duration),
+}
+
+impl TimeLimit {
+    pub fn duration(&self) -> Duration {
+        match self {
+            TimeLimit::Total(duration) | TimeLimit::PerGuess(duration) => *duration,
+        }
+    }
+}
+
+/// Input that gives up when the player takes too long. Reading from it fails
+/// with `io::ErrorKind::TimedOut` once the time limit has run out.
+pub struct TimedInput {
+    lines: Receiver<io::Result<String>>,
+    limit: TimeLimit,
+    deadline: Instant,
+    line: Vec<u8>,
+    pos: usize,
+}
+
+impl TimedInput {
+    /// Starts the clock and reads `inner` one line at a time.
+    pub fn new<R: BufRead + Send + 'static>(mut inner: R, limit: TimeLimit) -> TimedInput {
+        let (sender, lines) = mpsc::channel();
+
+        // A blocking read can't be cancelled, so it runs on its own thread and
+        // the game only waits for it as long as the clock allows.
+        thread::spawn(move || loop {
+            let mut line = String::new();
+            let result = inner.read_line(&mut line);
+            let done = !matches!(result, Ok(n) if n > 0);
+
+            if sender.send(result.map(|_| line)).is_err() || done {
+                break;
+            }
+        });
+
+        TimedInput {
+            lines,
+            limit,
+            deadline: Instant::now() + limit.duration(),
+            line: Vec::new(),
+            pos: 0,
+        }
+    }
+}
+
+impl Read for TimedInput {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl BufRead for TimedInput {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.pos == self.line.len() {
+            let timeout = self.deadline.saturating_duration_since(Instant::now());
+
+            match self.lines.recv_timeout(timeout) {
+                Ok(line) => {
+                    self.line = line?.into_bytes();
+                    self.pos = 0;
+
+                    if let TimeLimit::PerGuess(limit) = self.limit {
+                        self.deadline = Instant::now() + limit;
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(io::Error::new(io::ErrorKind::TimedOut, "time is up"));
+                }
+                // The reader thread stops after the end of the input, so
+                // there is nothing more to read.
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.line.clear();
+                    self.pos = 0;
+                }
+            }
+        }
+
+        Ok(&self.line[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = (self.pos + amt).min(self.line.len());
+    }
+}
+
+/// Points for a time-attack game: nothing for a loss, otherwise 1000 less 10
+/// for every second taken (but never below 100), plus 50 for every try left.
+pub fn points(won: bool, elapsed: Duration, attempts_left: u32) -> u32 {
+    if !won {
+        return 0;
+    }
+
+    let seconds = u32::try_from(elapsed.as_secs()).unwrap_or(u32::MAX);
+    let for_time = WIN_POINTS
+        .saturating_sub(seconds.saturating_mul(POINTS_PER_SECOND))
+        .max(MIN_WIN_POINTS);
+
+    for_time + attempts_left * POINTS_PER_TRY_LEFT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Hands out one line at a time, waiting before each one like a player
+    /// thinking about their guess.
+    struct Slow {
+        lines: Vec<(Duration, &'static str)>,
+    }
+
+    impl Read for Slow {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.lines.is_empty() {
+                return Ok(0);
+            }
+
+            let (delay, line) = self.lines.remove(0);
+            thread::sleep(delay);
+            buf[..line.len()].copy_from_slice(line.as_bytes());
+
+            Ok(line.len())
+        }
+    }
+
+    fn timed(lines: Vec<(Duration, &'static str)>, limit: TimeLimit) -> TimedInput {
+        TimedInput::new(io::BufReader::new(Slow { lines }), limit)
+    }
+
+    #[test]
+    fn reads_lines_until_the_end() {
+        let input = timed(
+            vec![(Duration::ZERO, "1\n"), (Duration::ZERO, "2\n")],
+            TimeLimit::Total(Duration::from_secs(5)),
+        );
+
+        let lines: Vec<String> = input.lines().map(Result::unwrap).collect();
+
+        assert_eq!(vec!["1", "2"], lines);
+    }
+
+    #[test]
+    fn total_limit_times_out() {
+        let mut input = timed(
+            vec![(Duration::ZERO, "1\n"), (Duration::from_secs(5), "2\n")],
+            TimeLimit::Total(Duration::from_secs(1)),
+        );
+        let mut line = String::new();
+
+        input.read_line(&mut line).unwrap();
+        let error = input.read_line(&mut line).unwrap_err();
+
+        assert_eq!("1\n", line);
+        assert_eq!(io::ErrorKind::TimedOut, error.kind());
+    }
+
+    #[test]
+    fn per_guess_limit_restarts_with_every_line() {
+        // Each line comes well within the limit, but all of them together
+        // take longer than it.
+        let delay = Duration::from_millis(250);
+        let input = timed(
+            vec![
+                (delay, "1\n"),
+                (delay, "2\n"),
+                (delay, "3\n"),
+                (delay, "4\n"),
+                (delay, "5\n"),
+            ],
+            TimeLimit::PerGuess(Duration::from_secs(1)),
+        );
+
+        assert_eq!(5, input.lines().map(Result::unwrap).count());
+    }
+
+    #[test]
+    fn points_reward_speed_and_spare_tries() {
+        assert_eq!(0, points(false, Duration::from_secs(1), 5));
+        assert_eq!(1000 - 120 + 150, points(true, Duration::from_secs(12), 3));
+        assert_eq!(100, points(true, Duration::from_secs(600), 0));
+    }
+}
@@ -0,0 +1,48 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use minigrep::{search, search_case_insensitive};
+
+const POEM: &str = include_str!("../poem.txt");
+
+// Roughly a megabyte of text where only a handful of lines contain the query,
+// which is the case the literal fast path is meant for.
+fn synthetic_contents() -> String {
+    let mut contents = String::new();
+
+    for i in 0..20_000 {
+        if i % 5_000 == 0 {
+            contents.push_str("a needle hidden somewhere in the haystack\n");
+        } else {
+            contents.push_str("the quick brown fox jumps over the lazy dog\n");
+        }
+    }
+
+    contents
+}
+
+fn poem(c: &mut Criterion) {
+    c.bench_function("poem/search", |b| {
+        b.iter(|| search(black_box("body"), black_box(POEM)))
+    });
+    c.bench_function("poem/search_case_insensitive", |b| {
+        b.iter(|| search_case_insensitive(black_box("BODY"), black_box(POEM)))
+    });
+}
+
+fn synthetic(c: &mut Criterion) {
+    let contents = synthetic_contents();
+
+    c.bench_function("synthetic/search_rare", |b| {
+        b.iter(|| search(black_box("needle"), black_box(&contents)))
+    });
+    c.bench_function("synthetic/search_every_line", |b| {
+        b.iter(|| search(black_box("fox"), black_box(&contents)))
+    });
+    c.bench_function("synthetic/search_case_insensitive", |b| {
+        b.iter(|| search_case_insensitive(black_box("NEEDLE"), black_box(&contents)))
+    });
+}
+
+criterion_group!(benches, poem, synthetic);
+criterion_main!(benches);
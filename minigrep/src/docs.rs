@@ -0,0 +1,329 @@
+use std::fmt::Write;
+
+use crate::flags::{Flag, ENV_VARS, FLAGS, POSITIONALS};
+
+pub const SHELLS: &[&str] = &["bash", "zsh", "fish"];
+
+/// What can come first instead of a query. `completions` takes one of `SHELLS`.
+pub const SUBCOMMANDS: &[&str] = &["completions", "man"];
+
+/// Returns the generated document when `args` ask for one, either
+/// `minigrep man` or `minigrep completions <shell>`, or an error naming the
+/// supported shells. Anything else is a search, so `minigrep man notes.txt`
+/// still looks for "man" in notes.txt.
+pub fn for_args(args: &[String]) -> Option<Result<String, String>> {
+    match args {
+        [_, command] if command == "man" => Some(Ok(man_page())),
+        [_, command, shell] if command == "completions" => {
+            Some(completions(shell).ok_or_else(|| {
+                format!(
+                    "unknown shell `{shell}`, expected one of {}",
+                    SHELLS.join("|")
+                )
+            }))
+        }
+        _ => None,
+    }
+}
+
+/// Generates the completion script for `shell`, or `None` if it is not one of
+/// `SHELLS`.
+pub fn completions(shell: &str) -> Option<String> {
+    match shell {
+        "bash" => Some(bash()),
+        "zsh" => Some(zsh()),
+        "fish" => Some(fish()),
+        _ => None,
+    }
+}
+
+fn spellings(flag: &Flag) -> Vec<String> {
+    let mut spellings = Vec::new();
+
+    if let Some(short) = flag.short {
+        spellings.push(format!("-{short}"));
+    }
+    spellings.push(format!("--{}", flag.long));
+
+    spellings
+}
+
+fn bash() -> String {
+    let flags: Vec<String> = FLAGS.iter().flat_map(spellings).collect();
+    let all = flags.join(" ");
+    let takes_value = flags.join("|");
+    let shells = SHELLS.join(" ");
+    let subcommands = SUBCOMMANDS.join(" ");
+
+    // Positionals with a fixed set of values offer them, the rest are files.
+    let mut positionals = String::new();
+    for (i, arg) in POSITIONALS.iter().enumerate() {
+        if !arg.values.is_empty() {
+            writeln!(
+                positionals,
+                "            {i}) COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")) ;;",
+                arg.values.join(" ")
+            )
+            .unwrap();
+        }
+    }
+
+    format!(
+        r#"_minigrep() {{
+    local cur prev word i positional=0
+    cur="${{COMP_WORDS[COMP_CWORD]}}"
+    prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+
+    case "$prev" in
+        {takes_value})
+            return 0
+            ;;
+        completions)
+            if [[ $COMP_CWORD -eq 2 ]]; then
+                COMPREPLY=($(compgen -W "{shells}" -- "$cur"))
+                return 0
+            fi
+            ;;
+    esac
+
+    # Count the positional arguments before the cursor, skipping flags and
+    # their values.
+    for ((i = 1; i < COMP_CWORD; i++)); do
+        word="${{COMP_WORDS[i]}}"
+        case "$word" in
+            {takes_value}) ((i++)) ;;
+            -*) ;;
+            *) ((positional++)) ;;
+        esac
+    done
+
+    if [[ "$cur" == -* ]]; then
+        COMPREPLY=($(compgen -W "{all}" -- "$cur"))
+    elif [[ $COMP_CWORD -eq 1 ]]; then
+        COMPREPLY=($(compgen -W "{subcommands}" -- "$cur"))
+    else
+        case $positional in
+{positionals}            *) COMPREPLY=($(compgen -f -- "$cur")) ;;
+        esac
+    fi
+}}
+
+complete -F _minigrep minigrep
+"#
+    )
+}
+
+fn zsh_escape(text: &str) -> String {
+    text.replace('\'', r"'\''")
+        .replace('[', r"\[")
+        .replace(']', r"\]")
+        .replace(':', r"\:")
+}
+
+fn zsh() -> String {
+    let mut script = format!(
+        "#compdef minigrep\n\n\
+         if [[ $words[2] == completions ]] && (( CURRENT == 3 )); then\n\
+         \x20   compadd {}\n\
+         \x20   return\n\
+         fi\n\n\
+         if (( CURRENT == 2 )) && [[ $PREFIX != -* ]]; then\n\
+         \x20   compadd {}\n\
+         fi\n\n\
+         _arguments \\\n",
+        SHELLS.join(" "),
+        SUBCOMMANDS.join(" ")
+    );
+
+    for flag in FLAGS {
+        let help = zsh_escape(flag.help);
+        let value = zsh_escape(flag.value_name);
+
+        match flag.short {
+            Some(short) => writeln!(
+                script,
+                "    '(-{short} --{long})'{{-{short},--{long}}}'[{help}]:{value}:' \\",
+                long = flag.long
+            ),
+            None => writeln!(script, "    '--{}[{help}]:{value}:' \\", flag.long),
+        }
+        .unwrap();
+    }
+
+    for (i, arg) in POSITIONALS.iter().enumerate() {
+        let optional = if arg.required { "" } else { ":" };
+        let action = match arg.name {
+            "FILE_PATH" => String::from("_files"),
+            _ if !arg.values.is_empty() => format!("({})", arg.values.join(" ")),
+            _ => String::new(),
+        };
+
+        writeln!(
+            script,
+            "    '{}{optional}:{}:{action}' \\",
+            i + 1,
+            zsh_escape(arg.help)
+        )
+        .unwrap();
+    }
+
+    // Drop the continuation after the last argument.
+    script.truncate(script.len() - 3);
+    script.push('\n');
+    script
+}
+
+fn fish() -> String {
+    let mut script = format!(
+        "complete -c minigrep -n '__fish_is_first_arg' -a '{}'\n\
+         complete -c minigrep -n '__fish_seen_subcommand_from completions' -x -a '{}'\n",
+        SUBCOMMANDS.join(" "),
+        SHELLS.join(" ")
+    );
+
+    for flag in FLAGS {
+        let help = flag.help.replace('\'', r"\'");
+
+        write!(script, "complete -c minigrep").unwrap();
+        if let Some(short) = flag.short {
+            write!(script, " -s {short}").unwrap();
+        }
+        writeln!(script, " -l {} -r -d '{help}'", flag.long).unwrap();
+    }
+
+    script
+}
+
+fn roff_escape(text: &str) -> String {
+    text.replace('\\', r"\e").replace('-', r"\-")
+}
+
+/// Generates the roff source of the `minigrep(1)` man page.
+pub fn man_page() -> String {
+    let mut page = String::new();
+
+    writeln!(
+        page,
+        ".TH MINIGREP 1 \"\" \"minigrep {}\"",
+        env!("CARGO_PKG_VERSION")
+    )
+    .unwrap();
+    page.push_str(".SH NAME\nminigrep \\- search for lines containing a query in a file\n");
+
+    page.push_str(".SH SYNOPSIS\n.B minigrep\n[\\fIOPTIONS\\fR]");
+    for arg in POSITIONALS {
+        let name = roff_escape(arg.name);
+        if arg.required {
+            write!(page, " \\fI{name}\\fR").unwrap();
+        } else {
+            write!(page, " [\\fI{name}\\fR]").unwrap();
+        }
+    }
+    writeln!(
+        page,
+        "\n.br\n.B minigrep completions\n{}\n.br\n.B minigrep man",
+        SHELLS
+            .iter()
+            .map(|shell| format!("\\fB{shell}\\fR"))
+            .collect::<Vec<_>>()
+            .join("|")
+    )
+    .unwrap();
+
+    page.push_str(".SH ARGUMENTS\n");
+    for arg in POSITIONALS {
+        writeln!(
+            page,
+            ".TP\n\\fI{}\\fR\n{}",
+            roff_escape(arg.name),
+            roff_escape(arg.help)
+        )
+        .unwrap();
+    }
+
+    page.push_str(".SH OPTIONS\n");
+    for flag in FLAGS {
+        let names: Vec<String> = spellings(flag)
+            .iter()
+            .map(|spelling| format!("\\fB{}\\fR", roff_escape(spelling)))
+            .collect();
+
+        writeln!(
+            page,
+            ".TP\n{} \\fI{}\\fR\n{}",
+            names.join(", "),
+            roff_escape(flag.value_name),
+            roff_escape(flag.help)
+        )
+        .unwrap();
+    }
+
+    page.push_str(".SH ENVIRONMENT\n");
+    for (name, help) in ENV_VARS {
+        writeln!(page, ".TP\n.B {}\n{}", roff_escape(name), roff_escape(help)).unwrap();
+    }
+
+    page
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_flag_is_documented_everywhere() {
+        let man = man_page();
+
+        for shell in SHELLS {
+            let script = completions(shell).unwrap();
+
+            for flag in FLAGS {
+                assert!(
+                    script.contains(flag.long),
+                    "{shell} is missing {}",
+                    flag.long
+                );
+            }
+
+            // Every script offers the subcommands, and the shells after
+            // `completions`.
+            assert!(script.contains(&SUBCOMMANDS.join(" ")), "{shell}");
+            assert!(script.contains(&SHELLS.join(" ")), "{shell}");
+        }
+
+        for flag in FLAGS {
+            assert!(man.contains(&roff_escape(flag.long)));
+            assert!(man.contains(&roff_escape(flag.help)));
+        }
+    }
+
+    #[test]
+    fn only_exact_subcommands_generate_docs() {
+        let args = |args: &[&str]| -> Vec<String> { args.iter().map(|a| a.to_string()).collect() };
+
+        assert!(for_args(&args(&["minigrep", "man"])).is_some());
+        assert!(for_args(&args(&["minigrep", "completions", "zsh"])).is_some());
+        assert!(for_args(&args(&["minigrep", "man", "poem.txt"])).is_none());
+        assert!(for_args(&args(&["minigrep", "completions", "zsh", "poem.txt"])).is_none());
+    }
+
+    #[test]
+    fn unknown_shells_are_rejected() {
+        let args = ["minigrep", "completions", "powershell"].map(String::from);
+
+        assert_eq!(
+            Some(Err(String::from(
+                "unknown shell `powershell`, expected one of bash|zsh|fish"
+            ))),
+            for_args(&args)
+        );
+    }
+
+    #[test]
+    fn bash_offers_values_only_for_their_positional() {
+        let script = bash();
+
+        assert!(script.contains("2) COMPREPLY=($(compgen -W \"true false\" -- \"$cur\")) ;;"));
+        assert!(script.contains("*) COMPREPLY=($(compgen -f -- \"$cur\")) ;;"));
+    }
+}
@@ -0,0 +1,214 @@
+use std::error::Error;
+
+use serde_json::Value;
+
+use crate::Limits;
+
+/// A record that could not be parsed, along with the line it starts on.
+#[derive(Debug, PartialEq)]
+pub struct Malformed {
+    pub line: u64,
+    pub message: String,
+}
+
+/// The records whose field matched the query, and the ones that were skipped
+/// because they could not be parsed.
+#[derive(Debug, Default, PartialEq)]
+pub struct FieldMatches<'a> {
+    pub records: Vec<&'a str>,
+    pub malformed: Vec<Malformed>,
+}
+
+/// Applies `is_match` to the named column of every CSV record in `contents`.
+/// The first record is the header row and is used to find the column.
+pub fn search_csv<'a>(
+    column: &str,
+    contents: &'a str,
+    limits: &Limits,
+    is_match: impl Fn(&str) -> bool,
+) -> Result<FieldMatches<'a>, Box<dyn Error>> {
+    let mut reader = csv::Reader::from_reader(contents.as_bytes());
+
+    let index = reader
+        .headers()?
+        .iter()
+        .position(|header| header == column)
+        .ok_or_else(|| format!("column `{column}` not found in CSV header"))?;
+
+    let mut matches = FieldMatches::default();
+    let mut record = csv::StringRecord::new();
+
+    while !limits.is_full(matches.records.len()) {
+        // After a CRLF the reader stops between the `\r` and the `\n`, so the
+        // record only starts once the line breaks are behind it.
+        let position = reader.position();
+        let rest = &contents.as_bytes()[position.byte() as usize..];
+        let breaks = rest.iter().take_while(|&&b| b == b'\r' || b == b'\n');
+        let skipped = breaks.clone().count();
+        let start = position.byte() as usize + skipped;
+        let line = position.line() as usize + breaks.filter(|&&b| b == b'\n').count();
+
+        if line > limits.end_line {
+            break;
+        }
+
+        match reader.read_record(&mut record) {
+            Ok(false) => break,
+            Ok(true) => {
+                let end = reader.position().byte() as usize;
+                let raw = contents[start..end].trim_end_matches(['\r', '\n']);
+
+                if limits.contains_line(line) && record.get(index).is_some_and(&is_match) {
+                    matches.records.push(raw);
+                }
+            }
+            Err(err) if limits.contains_line(line) => matches.malformed.push(Malformed {
+                line: line as u64,
+                message: err.to_string(),
+            }),
+            Err(_) => {}
+        }
+    }
+
+    Ok(matches)
+}
+
+/// Applies `is_match` to the field at `path` (for example `user.name` or
+/// `tags.0`) of every JSON Lines record in `contents`. Records without the
+/// field are not matches; records that are not valid JSON are malformed.
+pub fn search_json<'a>(
+    path: &str,
+    contents: &'a str,
+    limits: &Limits,
+    is_match: impl Fn(&str) -> bool,
+) -> FieldMatches<'a> {
+    let mut matches = FieldMatches::default();
+
+    let window = limits.window(contents).lines().take(limits.line_count());
+
+    for (number, line) in window.enumerate() {
+        if limits.is_full(matches.records.len()) {
+            break;
+        }
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let value: Value = match serde_json::from_str(line) {
+            Ok(value) => value,
+            Err(err) => {
+                matches.malformed.push(Malformed {
+                    line: (limits.start_line + number) as u64,
+                    message: err.to_string(),
+                });
+                continue;
+            }
+        };
+
+        let field = match lookup(&value, path) {
+            Some(Value::String(text)) => text.clone(),
+            Some(other) => other.to_string(),
+            None => continue,
+        };
+
+        if is_match(&field) {
+            matches.records.push(line);
+        }
+    }
+
+    matches
+}
+
+fn lookup<'v>(value: &'v Value, path: &str) -> Option<&'v Value> {
+    path.split('.').try_fold(value, |value, key| match value {
+        Value::Object(map) => map.get(key),
+        Value::Array(items) => items.get(key.parse::<usize>().ok()?),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_matches_only_the_named_column() {
+        let contents = "\
+name,city
+Rust,Berlin
+Ferris,Rustville
+\"Crab, the\",Rust City";
+
+        let matches = search_csv("name", contents, &Limits::default(), |field| {
+            field.contains("Rust")
+        })
+        .unwrap();
+
+        assert_eq!(vec!["Rust,Berlin"], matches.records);
+        assert!(matches.malformed.is_empty());
+    }
+
+    #[test]
+    fn csv_reports_malformed_records() {
+        let contents = "\
+name,city
+Rust,Berlin,extra
+Rusty,Paris";
+
+        let matches = search_csv("name", contents, &Limits::default(), |field| {
+            field.contains("Rust")
+        })
+        .unwrap();
+
+        assert_eq!(vec!["Rusty,Paris"], matches.records);
+        assert_eq!(2, matches.malformed[0].line);
+    }
+
+    #[test]
+    fn csv_with_crlf_line_endings() {
+        let contents = "name,city\r\nRust,Berlin,extra\r\nRusty,Paris\r\n";
+
+        let matches = search_csv("name", contents, &Limits::default(), |field| {
+            field.contains("Rust")
+        })
+        .unwrap();
+
+        assert_eq!(vec!["Rusty,Paris"], matches.records);
+        assert_eq!(2, matches.malformed[0].line);
+    }
+
+    #[test]
+    fn csv_line_window_counts_crlf_lines() {
+        let contents = "name,city\r\nRust,Oslo\r\nRust,Berlin\r\nRusty,Paris\r\n";
+        let limits = Limits {
+            start_line: 3,
+            end_line: 3,
+            ..Limits::default()
+        };
+
+        let matches =
+            search_csv("name", contents, &limits, |field| field.contains("Rust")).unwrap();
+
+        assert_eq!(vec!["Rust,Berlin"], matches.records);
+    }
+
+    #[test]
+    fn json_matches_nested_field_and_reports_malformed_lines() {
+        let contents = r#"{"user": {"name": "ferris"}, "text": "hello"}
+{"user": {"name": "corro"}, "text": "ferris says hi"}
+{"user": oops}
+{"text": "no user"}"#;
+
+        let matches = search_json("user.name", contents, &Limits::default(), |field| {
+            field.contains("ferris")
+        });
+
+        assert_eq!(
+            vec![r#"{"user": {"name": "ferris"}, "text": "hello"}"#],
+            matches.records
+        );
+        assert_eq!(1, matches.malformed.len());
+        assert_eq!(3, matches.malformed[0].line);
+    }
+}
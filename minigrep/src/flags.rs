@@ -0,0 +1,121 @@
+// The command line is described once here. `Config::build` parses with these
+// tables and the `docs` module generates completions and the man page from
+// them, so adding an option to one place updates all of them.
+
+/// What a flag sets on the `Config`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Opt {
+    CsvColumn,
+    JsonField,
+    MaxCount,
+    Lines,
+    MaxFilesize,
+}
+
+/// An option that takes a value, such as `--lines 10:20`.
+#[derive(Debug)]
+pub struct Flag {
+    pub opt: Opt,
+    pub long: &'static str,
+    pub short: Option<char>,
+    pub value_name: &'static str,
+    pub help: &'static str,
+    /// The parse error when the flag is the last argument.
+    pub missing_value: &'static str,
+}
+
+impl Flag {
+    pub fn matches(&self, arg: &str) -> bool {
+        match arg.strip_prefix("--") {
+            Some(long) => long == self.long,
+            None => self.short.is_some_and(|short| {
+                let mut chars = arg.chars();
+                chars.next() == Some('-') && chars.next() == Some(short) && chars.next().is_none()
+            }),
+        }
+    }
+}
+
+/// A positional argument, in the order it is expected.
+#[derive(Debug)]
+pub struct Positional {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub required: bool,
+    /// The only values accepted, when the argument is not free text.
+    pub values: &'static [&'static str],
+}
+
+pub const FLAGS: &[Flag] = &[
+    Flag {
+        opt: Opt::CsvColumn,
+        long: "csv-column",
+        short: None,
+        value_name: "NAME",
+        help: "Treat the file as CSV and search only the column with this header",
+        missing_value: "--csv-column and --json-field need a field name",
+    },
+    Flag {
+        opt: Opt::JsonField,
+        long: "json-field",
+        short: None,
+        value_name: "PATH",
+        help: "Treat the file as JSON Lines and search only this dotted field path",
+        missing_value: "--csv-column and --json-field need a field name",
+    },
+    Flag {
+        opt: Opt::MaxCount,
+        long: "max-count",
+        short: Some('m'),
+        value_name: "NUM",
+        help: "Stop after NUM matching lines or records",
+        missing_value: "-m needs a number of matches",
+    },
+    Flag {
+        opt: Opt::Lines,
+        long: "lines",
+        short: None,
+        value_name: "START:END",
+        help: "Search only lines START to END, either of which may be left out",
+        missing_value: "--lines needs START:END",
+    },
+    Flag {
+        opt: Opt::MaxFilesize,
+        long: "max-filesize",
+        short: None,
+        value_name: "SIZE",
+        help: "Skip the file if it is larger than SIZE bytes, with an optional K, M or G suffix",
+        missing_value: "--max-filesize needs a size",
+    },
+];
+
+pub const POSITIONALS: &[Positional] = &[
+    Positional {
+        name: "QUERY",
+        help: "The text to search for",
+        required: true,
+        values: &[],
+    },
+    Positional {
+        name: "FILE_PATH",
+        help: "The file to search",
+        required: true,
+        values: &[],
+    },
+    Positional {
+        name: "IGNORE_CASE",
+        help: "Search without regard to case when true",
+        required: false,
+        values: &["true", "false"],
+    },
+];
+
+/// Environment variables read by `Config::build`, with their descriptions.
+pub const ENV_VARS: &[(&str, &str)] = &[(
+    "IGNORE_CASE",
+    "Search without regard to case when set to true. The IGNORE_CASE argument can turn this on but not off.",
+)];
+
+pub fn find(arg: &str) -> Option<&'static Flag> {
+    FLAGS.iter().find(|flag| flag.matches(arg))
+}
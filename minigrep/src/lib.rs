@@ -1,19 +1,85 @@
 use std::{env, error::Error, fs};
 
+use memchr::{memchr, memchr_iter, memmem, memrchr};
+
+pub mod docs;
+mod fields;
+mod flags;
+mod limits;
+
+pub use fields::{search_csv, search_json, FieldMatches, Malformed};
+pub use limits::Limits;
+
+use flags::Opt;
+
 pub struct Config {
     query: String,
     file_path: String,
     ignore_case: bool,
+    field: Option<Field>,
+    limits: Limits,
+}
+
+/// Restricts the query to one field of each record instead of the whole line.
+#[derive(Debug, PartialEq)]
+pub enum Field {
+    /// A column of a CSV file, looked up by its header name.
+    Csv(String),
+    /// A dotted path into each object of a JSON Lines file.
+    Json(String),
 }
 
 impl Config {
     pub fn build(args: &[String]) -> Result<Config, &'static str> {
-        if args.len() < 3 {
+        let mut positional: Vec<String> = Vec::new();
+        let mut field = None;
+        let mut limits = Limits::default();
+
+        let mut args_iter = args.iter().skip(1);
+        while let Some(arg) = args_iter.next() {
+            let flag = match flags::find(arg) {
+                Some(flag) => flag,
+                None => {
+                    positional.push(arg.clone());
+                    continue;
+                }
+            };
+
+            let value = args_iter.next().ok_or(flag.missing_value)?;
+
+            match flag.opt {
+                Opt::CsvColumn | Opt::JsonField => {
+                    if field.is_some() {
+                        return Err("only one of --csv-column and --json-field can be used");
+                    }
+
+                    field = if flag.opt == Opt::CsvColumn {
+                        Some(Field::Csv(value.clone()))
+                    } else {
+                        Some(Field::Json(value.clone()))
+                    };
+                }
+                Opt::MaxCount => {
+                    let max_count = value
+                        .parse()
+                        .map_err(|_| "-m must be a number of matches")?;
+                    limits.max_count = Some(max_count);
+                }
+                Opt::Lines => {
+                    (limits.start_line, limits.end_line) = limits::parse_line_range(value)?;
+                }
+                Opt::MaxFilesize => {
+                    limits.max_filesize = Some(limits::parse_size(value)?);
+                }
+            }
+        }
+
+        if positional.len() < 2 {
             return Err("not enough arguments");
         }
 
-        let query = args[1].clone();
-        let file_path = args[2].clone();
+        let query = positional[0].clone();
+        let file_path = positional[1].clone();
         let mut ignore_case = false;
 
         let is_env_ignore_case_set = env::var("IGNORE_CASE").is_ok();
@@ -28,8 +94,8 @@ impl Config {
             ignore_case = value
         }
 
-        if args.len() > 3 {
-            let is_argument_ignore_case: bool = match args[3].clone().parse() {
+        if positional.len() > 2 {
+            let is_argument_ignore_case: bool = match positional[2].parse() {
                 Ok(value) => value,
                 Err(_) => {
                     return Err("third argument must be a boolean value");
@@ -45,17 +111,35 @@ impl Config {
             query,
             file_path,
             ignore_case,
+            field,
+            limits,
         })
     }
 }
 
 pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
+    if let Some(max_filesize) = config.limits.max_filesize {
+        let size = fs::metadata(&config.file_path)?.len();
+
+        if size > max_filesize {
+            eprintln!(
+                "{}: skipped, {size} bytes is over --max-filesize",
+                config.file_path
+            );
+            return Ok(());
+        }
+    }
+
     let contents = fs::read_to_string(&config.file_path)?;
 
+    if let Some(field) = &config.field {
+        return run_fields(&config, field, &contents);
+    }
+
     let results = if config.ignore_case {
-        search_case_insensitive(&config.query, &contents)
+        search_case_insensitive_limited(&config.query, &contents, &config.limits)
     } else {
-        search(&config.query, &contents)
+        search_limited(&config.query, &contents, &config.limits)
     };
 
     for line in results {
@@ -65,10 +149,98 @@ pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-fn search<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
+fn run_fields(config: &Config, field: &Field, contents: &str) -> Result<(), Box<dyn Error>> {
+    let query = config.query.to_lowercase();
+    let is_match = |value: &str| {
+        if config.ignore_case {
+            value.to_lowercase().contains(&query)
+        } else {
+            value.contains(&config.query)
+        }
+    };
+
+    let results = match field {
+        Field::Csv(column) => search_csv(column, contents, &config.limits, is_match)?,
+        Field::Json(path) => search_json(path, contents, &config.limits, is_match),
+    };
+
+    for line in results.records {
+        println!("{line}");
+    }
+
+    // A bad record shouldn't hide the matches in the rest of the file, so we
+    // report it and keep going.
+    for malformed in results.malformed {
+        eprintln!(
+            "{}:{}: malformed record: {}",
+            config.file_path, malformed.line, malformed.message
+        );
+    }
+
+    Ok(())
+}
+
+pub fn search<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
+    search_limited(query, contents, &Limits::default())
+}
+
+pub fn search_limited<'a>(query: &str, contents: &'a str, limits: &Limits) -> Vec<&'a str> {
+    let contents = limits.window(contents);
+
+    // An empty query matches every line, and a query spanning a line break can
+    // never match a single line, so only plain literals take the fast path.
+    if query.is_empty() || query.contains(['\n', '\r']) {
+        return search_lines(query, contents, limits);
+    }
+
+    let finder = memmem::Finder::new(query);
+    let bytes = contents.as_bytes();
     let mut result: Vec<&str> = Vec::new();
+    let mut pos = 0;
+
+    // Lines only need counting when the window ends before the file does.
+    let bounded = limits.end_line < usize::MAX;
+    let mut line = 1;
+
+    // Scan the whole buffer for the query and only look for the surrounding
+    // line breaks once we have a hit, instead of splitting every line first.
+    while !limits.is_full(result.len()) {
+        let offset = match finder.find(&bytes[pos..]) {
+            Some(offset) => offset,
+            None => break,
+        };
+        let hit = pos + offset;
+        let start = memrchr(b'\n', &bytes[pos..hit]).map_or(pos, |i| pos + i + 1);
+
+        if bounded {
+            line += memchr_iter(b'\n', &bytes[pos..start]).count();
+            if line > limits.line_count() {
+                break;
+            }
+        }
+
+        let (end, next) = match memchr(b'\n', &bytes[hit..]) {
+            Some(i) if i > 0 && bytes[hit + i - 1] == b'\r' => (hit + i - 1, hit + i + 1),
+            Some(i) => (hit + i, hit + i + 1),
+            None => (bytes.len(), bytes.len()),
+        };
+
+        result.push(&contents[start..end]);
+        pos = next;
+        line += 1;
+    }
+
+    result
+}
+
+fn search_lines<'a>(query: &str, contents: &'a str, limits: &Limits) -> Vec<&'a str> {
+    let mut result: Vec<&str> = Vec::new();
+
+    for lines in contents.lines().take(limits.line_count()) {
+        if limits.is_full(result.len()) {
+            break;
+        }
 
-    for lines in contents.lines() {
         if lines.contains(query) {
             result.push(lines);
         }
@@ -77,10 +249,22 @@ fn search<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
     result
 }
 
-fn search_case_insensitive<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
+pub fn search_case_insensitive<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
+    search_case_insensitive_limited(query, contents, &Limits::default())
+}
+
+pub fn search_case_insensitive_limited<'a>(
+    query: &str,
+    contents: &'a str,
+    limits: &Limits,
+) -> Vec<&'a str> {
     let mut result: Vec<&str> = Vec::new();
 
-    for lines in contents.lines() {
+    for lines in limits.window(contents).lines().take(limits.line_count()) {
+        if limits.is_full(result.len()) {
+            break;
+        }
+
         if lines.to_lowercase().contains(&query.to_lowercase()) {
             result.push(lines);
         }
@@ -105,6 +289,59 @@ Duct tape.";
         assert_eq!(vec!["safe, fast, productive."], search(query, contents));
     }
 
+    #[test]
+    fn literal_search_matches_line_by_line_search() {
+        let query = "to";
+        let contents = "to be or not to be\r\nnothing\n\ntoday is\r\nthe end, too";
+
+        assert_eq!(
+            vec!["to be or not to be", "today is", "the end, too"],
+            search(query, contents)
+        );
+        assert_eq!(
+            search_lines(query, contents, &Limits::default()),
+            search(query, contents)
+        );
+    }
+
+    #[test]
+    fn limits_stop_the_search_early() {
+        let contents = "one fish\ntwo fish\nred fish\nblue fish";
+        let limits = Limits {
+            max_count: Some(2),
+            start_line: 2,
+            ..Limits::default()
+        };
+
+        assert_eq!(
+            vec!["two fish", "red fish"],
+            search_limited("fish", contents, &limits)
+        );
+        assert_eq!(
+            vec!["two fish", "red fish"],
+            search_case_insensitive_limited("FISH", contents, &limits)
+        );
+    }
+
+    #[test]
+    fn line_window_ends_the_literal_search() {
+        let contents = "one fish\ntwo fish\n\nred fish\nblue fish";
+        let limits = Limits {
+            start_line: 2,
+            end_line: 4,
+            ..Limits::default()
+        };
+
+        assert_eq!(
+            vec!["two fish", "red fish"],
+            search_limited("fish", contents, &limits)
+        );
+        assert_eq!(
+            search_lines("fish", limits.window(contents), &limits),
+            search_limited("fish", contents, &limits)
+        );
+    }
+
     #[test]
     fn case_insensitive() {
         let query = "rUst";
@@ -0,0 +1,152 @@
+use memchr::memchr_iter;
+
+/// Bounds on how much of a file is searched. They are checked while scanning
+/// so the search stops as soon as a bound is reached.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Limits {
+    /// Stop after this many matching lines or records.
+    pub max_count: Option<usize>,
+    /// First line to search, counting from 1.
+    pub start_line: usize,
+    /// Last line to search, inclusive.
+    pub end_line: usize,
+    /// Skip files larger than this many bytes.
+    pub max_filesize: Option<u64>,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_count: None,
+            start_line: 1,
+            end_line: usize::MAX,
+            max_filesize: None,
+        }
+    }
+}
+
+impl Limits {
+    pub fn contains_line(&self, line: usize) -> bool {
+        self.start_line <= line && line <= self.end_line
+    }
+
+    pub fn is_full(&self, found: usize) -> bool {
+        self.max_count.is_some_and(|max| found >= max)
+    }
+
+    /// How many lines the window covers, or 0 if it ends before it starts.
+    pub fn line_count(&self) -> usize {
+        match self.end_line.checked_sub(self.start_line) {
+            Some(lines) => lines.saturating_add(1),
+            None => 0,
+        }
+    }
+
+    /// Returns `contents` from the first line of the window on, or "" if the
+    /// window is empty. Where the window ends is left to the search loop, so
+    /// `-m` can stop the search before the last line is ever found.
+    pub fn window<'a>(&self, contents: &'a str) -> &'a str {
+        if self.line_count() == 0 {
+            return "";
+        }
+
+        let start = if self.start_line > 1 {
+            match memchr_iter(b'\n', contents.as_bytes()).nth(self.start_line - 2) {
+                Some(i) => i + 1,
+                None => return "",
+            }
+        } else {
+            0
+        };
+
+        &contents[start..]
+    }
+}
+
+/// Parses a `START:END` line window. Either side may be left out, so `:20`
+/// and `10:` are accepted too.
+pub fn parse_line_range(value: &str) -> Result<(usize, usize), &'static str> {
+    let (start, end) = match value.split_once(':') {
+        Some(range) => range,
+        None => return Err("--lines must look like START:END"),
+    };
+
+    let start = match start {
+        "" => 1,
+        start => start
+            .parse()
+            .map_err(|_| "--lines START must be a line number")?,
+    };
+    let end = match end {
+        "" => usize::MAX,
+        end => end
+            .parse()
+            .map_err(|_| "--lines END must be a line number")?,
+    };
+
+    if start == 0 || start > end {
+        return Err("--lines needs 1 <= START <= END");
+    }
+
+    Ok((start, end))
+}
+
+/// Parses a size in bytes with an optional `K`, `M` or `G` suffix.
+pub fn parse_size(value: &str) -> Result<u64, &'static str> {
+    let (digits, multiplier) = match value.char_indices().last() {
+        Some((i, 'K' | 'k')) => (&value[..i], 1 << 10),
+        Some((i, 'M' | 'm')) => (&value[..i], 1 << 20),
+        Some((i, 'G' | 'g')) => (&value[..i], 1 << 30),
+        _ => (value, 1),
+    };
+
+    digits
+        .parse::<u64>()
+        .ok()
+        .and_then(|size| size.checked_mul(multiplier))
+        .ok_or("--max-filesize must be a size like 4096, 512K or 10M")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_keeps_only_the_requested_lines() {
+        let contents = "one\ntwo\nthree\nfour\n";
+        let limits = Limits {
+            start_line: 2,
+            end_line: 3,
+            ..Limits::default()
+        };
+
+        assert_eq!(2, limits.line_count());
+        assert_eq!("two\nthree\nfour\n", limits.window(contents));
+        let past_the_end = Limits {
+            start_line: 9,
+            ..Limits::default()
+        };
+        assert_eq!("", past_the_end.window(contents));
+    }
+
+    #[test]
+    fn window_that_ends_before_it_starts_is_empty() {
+        let backwards = Limits {
+            start_line: 5,
+            end_line: 2,
+            ..Limits::default()
+        };
+
+        assert_eq!(0, backwards.line_count());
+        assert_eq!("", backwards.window("one\ntwo\nthree\nfour\nfive\nsix\n"));
+    }
+
+    #[test]
+    fn parses_line_ranges_and_sizes() {
+        assert_eq!(Ok((5, 10)), parse_line_range("5:10"));
+        assert_eq!(Ok((1, usize::MAX)), parse_line_range(":"));
+        assert!(parse_line_range("10:5").is_err());
+        assert_eq!(Ok(512 * 1024), parse_size("512K"));
+        assert!(parse_size("lots").is_err());
+    }
+}
@@ -5,6 +5,16 @@ use minigrep::Config;
 fn main() {
     let args: Vec<String> = env::args().collect();
 
+    if let Some(doc) = minigrep::docs::for_args(&args) {
+        let doc = doc.unwrap_or_else(|err| {
+            eprintln!("Problem parsing arguments: {err}");
+            process::exit(1);
+        });
+
+        print!("{doc}");
+        return;
+    }
+
     let config = Config::build(&args).unwrap_or_else(|err| {
         eprintln!("Problem parsing arguments: {}", err);
         process::exit(1);
@@ -0,0 +1,279 @@
+mod common;
+
+use common::{fixture, minigrep};
+
+#[test]
+fn prints_matching_lines() {
+    let poem = fixture("poem.txt");
+    let run = minigrep(&["to", &poem], None);
+
+    assert_eq!(Some(0), run.code);
+    assert_eq!(
+        "Are you nobody, too?\nHow dreary to be somebody!\n",
+        run.stdout
+    );
+    assert_eq!("", run.stderr);
+}
+
+#[test]
+fn prints_nothing_without_matches() {
+    let poem = fixture("poem.txt");
+    let run = minigrep(&["monomorphization", &poem], None);
+
+    assert_eq!(Some(0), run.code);
+    assert_eq!("", run.stdout);
+}
+
+#[test]
+fn ignores_case_with_third_argument() {
+    let poem = fixture("poem.txt");
+    let run = minigrep(&["to", &poem, "true"], None);
+
+    assert_eq!(Some(0), run.code);
+    assert_eq!(
+        "Are you nobody, too?\nHow dreary to be somebody!\nTo tell your name the livelong day\nTo an admiring bog!\n",
+        run.stdout
+    );
+}
+
+#[test]
+fn ignores_case_with_env_var() {
+    let poem = fixture("poem.txt");
+    let run = minigrep(&["TO", &poem], Some("true"));
+
+    assert_eq!(Some(0), run.code);
+    assert_eq!(4, run.stdout.lines().count());
+}
+
+#[test]
+fn argument_cannot_turn_off_env_var() {
+    let poem = fixture("poem.txt");
+    let run = minigrep(&["TO", &poem, "false"], Some("true"));
+
+    assert_eq!(Some(0), run.code);
+    assert_eq!(4, run.stdout.lines().count());
+}
+
+#[test]
+fn argument_overrides_false_env_var() {
+    let poem = fixture("poem.txt");
+    let run = minigrep(&["TO", &poem, "true"], Some("false"));
+
+    assert_eq!(Some(0), run.code);
+    assert_eq!(4, run.stdout.lines().count());
+}
+
+#[test]
+fn false_env_var_and_no_argument_is_case_sensitive() {
+    let poem = fixture("poem.txt");
+    let run = minigrep(&["To", &poem], Some("false"));
+
+    assert_eq!(Some(0), run.code);
+    assert_eq!(
+        "To tell your name the livelong day\nTo an admiring bog!\n",
+        run.stdout
+    );
+}
+
+#[test]
+fn rejects_non_boolean_env_var() {
+    let poem = fixture("poem.txt");
+    let run = minigrep(&["to", &poem], Some("yes"));
+
+    assert_eq!(Some(1), run.code);
+    assert_eq!("", run.stdout);
+    assert_eq!(
+        "Problem parsing arguments: IGNORE_CASE must be a boolean\n",
+        run.stderr
+    );
+}
+
+#[test]
+fn rejects_non_boolean_third_argument() {
+    let poem = fixture("poem.txt");
+    let run = minigrep(&["to", &poem, "yes"], None);
+
+    assert_eq!(Some(1), run.code);
+    assert_eq!(
+        "Problem parsing arguments: third argument must be a boolean value\n",
+        run.stderr
+    );
+}
+
+#[test]
+fn rejects_missing_arguments() {
+    let run = minigrep(&["to"], None);
+
+    assert_eq!(Some(1), run.code);
+    assert_eq!("", run.stdout);
+    assert_eq!(
+        "Problem parsing arguments: not enough arguments\n",
+        run.stderr
+    );
+}
+
+#[test]
+fn reports_missing_file() {
+    let missing = fixture("missing.txt");
+    let run = minigrep(&["to", &missing], None);
+
+    assert_eq!(Some(1), run.code);
+    assert_eq!("", run.stdout);
+    assert!(run.stderr.starts_with("Application error: "));
+}
+
+#[test]
+fn searches_csv_column_and_reports_malformed_records() {
+    let people = fixture("records/people.csv");
+    let run = minigrep(&["Rust", &people, "--csv-column", "name"], None);
+
+    assert_eq!(Some(0), run.code);
+    assert_eq!("Ann Rust,Oslo\n", run.stdout);
+    assert!(run
+        .stderr
+        .starts_with(&format!("{people}:4: malformed record: ")));
+}
+
+#[test]
+fn searches_csv_with_crlf_line_endings() {
+    let people = fixture("records/people_crlf.csv");
+    let run = minigrep(&["Rust", &people, "--csv-column", "name"], None);
+
+    assert_eq!(Some(0), run.code);
+    assert_eq!("Ann Rust,Oslo\n", run.stdout);
+    assert!(run
+        .stderr
+        .starts_with(&format!("{people}:4: malformed record: ")));
+}
+
+#[test]
+fn reports_unknown_csv_column() {
+    let people = fixture("records/people.csv");
+    let run = minigrep(&["Rust", &people, "--csv-column", "country"], None);
+
+    assert_eq!(Some(1), run.code);
+    assert_eq!(
+        "Application error: column `country` not found in CSV header\n",
+        run.stderr
+    );
+}
+
+#[test]
+fn searches_json_field_and_reports_malformed_records() {
+    let events = fixture("records/events.jsonl");
+    let run = minigrep(
+        &["ferris", &events, "--json-field", "user.name", "true"],
+        None,
+    );
+
+    assert_eq!(Some(0), run.code);
+    assert_eq!(
+        "{\"user\": {\"name\": \"ferris\"}, \"text\": \"hello\"}\n{\"user\": {\"name\": \"Ferris\"}, \"text\": \"bye\"}\n",
+        run.stdout
+    );
+    assert!(run
+        .stderr
+        .starts_with(&format!("{events}:3: malformed record: ")));
+}
+
+#[test]
+fn rejects_both_field_flags() {
+    let events = fixture("records/events.jsonl");
+    let run = minigrep(
+        &["a", &events, "--json-field", "a", "--csv-column", "b"],
+        None,
+    );
+
+    assert_eq!(Some(1), run.code);
+    assert_eq!(
+        "Problem parsing arguments: only one of --csv-column and --json-field can be used\n",
+        run.stderr
+    );
+}
+
+#[test]
+fn stops_after_max_count() {
+    let poem = fixture("poem.txt");
+    let run = minigrep(&["-m", "1", "to", &poem, "true"], None);
+
+    assert_eq!(Some(0), run.code);
+    assert_eq!("Are you nobody, too?\n", run.stdout);
+}
+
+#[test]
+fn searches_only_the_line_window() {
+    let poem = fixture("poem.txt");
+    let run = minigrep(&["to", &poem, "true", "--lines", "6:8"], None);
+
+    assert_eq!(Some(0), run.code);
+    assert_eq!(
+        "How dreary to be somebody!\nTo tell your name the livelong day\n",
+        run.stdout
+    );
+}
+
+#[test]
+fn rejects_bad_line_window() {
+    let poem = fixture("poem.txt");
+    let run = minigrep(&["to", &poem, "--lines", "8:6"], None);
+
+    assert_eq!(Some(1), run.code);
+    assert_eq!(
+        "Problem parsing arguments: --lines needs 1 <= START <= END\n",
+        run.stderr
+    );
+}
+
+#[test]
+fn skips_files_over_max_filesize() {
+    let poem = fixture("poem.txt");
+    let run = minigrep(&["to", &poem, "--max-filesize", "100"], None);
+
+    assert_eq!(Some(0), run.code);
+    assert_eq!("", run.stdout);
+    assert!(run.stderr.contains("is over --max-filesize"));
+
+    let run = minigrep(&["to", &poem, "--max-filesize", "1K"], None);
+    assert_eq!(2, run.stdout.lines().count());
+}
+
+#[test]
+fn generates_man_page() {
+    let run = minigrep(&["man"], None);
+
+    assert_eq!(Some(0), run.code);
+    assert!(run.stdout.starts_with(".TH MINIGREP 1"));
+    assert!(run.stdout.contains(r"\fB\-\-max\-filesize\fR"));
+}
+
+#[test]
+fn generates_completions_for_each_shell() {
+    for shell in ["bash", "zsh", "fish"] {
+        let run = minigrep(&["completions", shell], None);
+
+        assert_eq!(Some(0), run.code);
+        assert!(run.stdout.contains("csv-column"), "{shell}");
+    }
+}
+
+#[test]
+fn rejects_unknown_shells() {
+    let run = minigrep(&["completions", "powershell"], None);
+
+    assert_eq!(Some(1), run.code);
+    assert_eq!("", run.stdout);
+    assert!(
+        run.stderr.contains("expected one of bash|zsh|fish"),
+        "{}",
+        run.stderr
+    );
+}
+
+#[test]
+fn searches_for_subcommand_names_when_given_a_file() {
+    let poem = fixture("poem.txt");
+    let run = minigrep(&["man", &poem], None);
+
+    assert_eq!(Some(0), run.code);
+    assert_eq!("", run.stdout);
+}
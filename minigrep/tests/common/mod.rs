@@ -0,0 +1,43 @@
+use std::{
+    path::PathBuf,
+    process::{Command, Output},
+};
+
+/// The result of one run of the minigrep binary.
+pub struct Run {
+    pub stdout: String,
+    pub stderr: String,
+    pub code: Option<i32>,
+}
+
+pub fn fixture(name: &str) -> String {
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "fixtures", name]
+        .iter()
+        .collect();
+
+    path.to_string_lossy().into_owned()
+}
+
+/// Runs minigrep with `args`, with IGNORE_CASE set to `ignore_case` or
+/// removed from the environment when it is `None`.
+pub fn minigrep(args: &[&str], ignore_case: Option<&str>) -> Run {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_minigrep"));
+    command.args(args);
+
+    match ignore_case {
+        Some(value) => command.env("IGNORE_CASE", value),
+        None => command.env_remove("IGNORE_CASE"),
+    };
+
+    let Output {
+        status,
+        stdout,
+        stderr,
+    } = command.output().expect("failed to run minigrep");
+
+    Run {
+        stdout: String::from_utf8(stdout).unwrap(),
+        stderr: String::from_utf8(stderr).unwrap(),
+        code: status.code(),
+    }
+}
@@ -0,0 +1,194 @@
+// A feed holds items of any type that implements Summary. Each item is a
+// trait object, Box<dyn Summary>, so a Tweet and a NewsArticle can sit in the
+// same Vec even though they are different types.
+
+use std::time::SystemTime;
+
+use crate::Summary;
+
+pub struct Entry {
+    pub published: SystemTime,
+    pub item: Box<dyn Summary>,
+}
+
+/// Items of mixed types, newest first.
+#[derive(Default)]
+pub struct Feed {
+    entries: Vec<Entry>,
+}
+
+impl Feed {
+    pub fn new() -> Feed {
+        Feed::default()
+    }
+
+    /// Adds `item` where it belongs by time. Items published at the same time
+    /// keep the order they were added in.
+    pub fn push(&mut self, published: SystemTime, item: impl Summary + 'static) {
+        let at = self
+            .entries
+            .partition_point(|entry| entry.published >= published);
+
+        self.entries.insert(
+            at,
+            Entry {
+                published,
+                item: Box::new(item),
+            },
+        );
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Entry> {
+        self.entries.iter()
+    }
+
+    /// The items about `tag`. A tag starting with `#` or `@` has to be one of
+    /// the item's tags, a plain word can also be one of its keywords. Case
+    /// doesn't matter.
+    pub fn tagged(&self, tag: &str) -> impl Iterator<Item = &Entry> + '_ {
+        let tag = tag.to_lowercase();
+
+        self.entries.iter().filter(move |entry| {
+            let item = &entry.item;
+            let is_tag = |t: &String| t.to_lowercase() == tag;
+
+            if tag.starts_with(['#', '@']) {
+                item.tags().iter().any(is_tag)
+            } else {
+                item.keywords().contains(&tag)
+                    || item.tags().iter().any(|t| t[1..].to_lowercase() == tag)
+            }
+        })
+    }
+
+    /// How many pages of `per_page` items the feed fills. Pages of no items
+    /// hold nothing, so there are none of them.
+    pub fn pages(&self, per_page: usize) -> usize {
+        if per_page == 0 {
+            return 0;
+        }
+
+        self.entries.len().div_ceil(per_page)
+    }
+
+    /// The page at `index`, counting from 0, or `None` past the last page.
+    pub fn page(&self, index: usize, per_page: usize) -> Option<&[Entry]> {
+        if per_page == 0 {
+            return None;
+        }
+
+        self.entries.chunks(per_page).nth(index)
+    }
+
+    /// The `n` newest items as one "Breaking news!" message, one numbered
+    /// summary per line.
+    pub fn digest(&self, n: usize) -> String {
+        let mut digest = String::from("Breaking news!");
+
+        for (i, entry) in self.entries.iter().take(n).enumerate() {
+            digest.push_str(&format!("\n{}. {}", i + 1, entry.item.summarize()));
+        }
+
+        digest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NewsArticle, Tweet};
+    use std::time::Duration;
+
+    fn at(seconds: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    fn tweet(content: &str) -> Tweet {
+        Tweet {
+            id: 1,
+            username: String::from("horse_ebooks"),
+            content: content.to_string(),
+            reply_to: None,
+            retweet_of: None,
+        }
+    }
+
+    fn feed() -> Feed {
+        let mut feed = Feed::new();
+
+        feed.push(at(20), tweet("second"));
+        feed.push(
+            at(30),
+            NewsArticle {
+                headline: String::from("Third"),
+                location: String::from("Pittsburgh"),
+                author: String::from("Iceburgh"),
+                content: String::new(),
+            },
+        );
+        feed.push(at(10), tweet("first"));
+        feed.push(at(20), tweet("also second"));
+
+        feed
+    }
+
+    #[test]
+    fn keeps_the_newest_first() {
+        let summaries: Vec<String> = feed().iter().map(|e| e.item.summarize()).collect();
+
+        assert_eq!(
+            vec![
+                "Third, by Iceburgh (Pittsburgh)",
+                "horse_ebooks: second",
+                "horse_ebooks: also second",
+                "horse_ebooks: first",
+            ],
+            summaries
+        );
+    }
+
+    #[test]
+    fn splits_into_pages() {
+        let feed = feed();
+
+        assert_eq!(2, feed.pages(3));
+        assert_eq!(3, feed.page(0, 3).unwrap().len());
+        assert_eq!(at(10), feed.page(1, 3).unwrap()[0].published);
+        assert!(feed.page(2, 3).is_none());
+        assert_eq!(0, feed.pages(0));
+        assert!(feed.page(0, 0).is_none());
+    }
+
+    #[test]
+    fn digest_lists_the_newest_items() {
+        assert_eq!(
+            "Breaking news!\n1. Third, by Iceburgh (Pittsburgh)\n2. horse_ebooks: second",
+            feed().digest(2)
+        );
+        assert_eq!("Breaking news!", Feed::new().digest(5));
+    }
+
+    #[test]
+    fn finds_items_by_tag() {
+        let mut feed = feed();
+        feed.push(at(40), tweet("Learning #Rust with @ferris"));
+        let found = |tag| -> Vec<String> { feed.tagged(tag).map(|e| e.item.summarize()).collect() };
+
+        assert_eq!(
+            vec!["horse_ebooks: Learning #Rust with @ferris"],
+            found("#rust")
+        );
+        assert_eq!(found("#rust"), found("Rust"));
+        assert_eq!(found("#rust"), found("@Ferris"));
+        assert_eq!(vec!["Third, by Iceburgh (Pittsburgh)"], found("third"));
+        assert!(found("#third").is_empty());
+    }
+}
@@ -0,0 +1,108 @@
+// A feed from outside can mix articles and tweets. Each record says which it
+// is in a "type" field, and the enum turns it back into the right struct:
+//
+//   {"type": "tweet", "username": "horse_ebooks", "content": "...", ...}
+//   {"type": "news_article", "headline": "...", "author": "...", ...}
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{render::Renderer, NewsArticle, Summary, Tweet};
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
+pub enum Item {
+    NewsArticle(NewsArticle),
+    Tweet(Tweet),
+}
+
+impl Item {
+    fn summary(&self) -> &dyn Summary {
+        match self {
+            Item::NewsArticle(article) => article,
+            Item::Tweet(tweet) => tweet,
+        }
+    }
+}
+
+// Every method is passed on, so an Item summarizes exactly like the struct
+// inside it, overrides included.
+impl Summary for Item {
+    fn summarize_author(&self) -> String {
+        self.summary().summarize_author()
+    }
+
+    fn summarize(&self) -> String {
+        self.summary().summarize()
+    }
+
+    fn render(&self, renderer: &dyn Renderer) -> String {
+        self.summary().render(renderer)
+    }
+
+    fn keywords(&self) -> Vec<String> {
+        self.summary().keywords()
+    }
+
+    fn tags(&self) -> Vec<String> {
+        self.summary().tags()
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mixed_feed_deserializes_into_each_type() {
+        let json = r#"[
+            {"type": "tweet", "id": 2, "username": "horse_ebooks", "content": "of course",
+             "retweet_of": 1},
+            {"type": "news_article", "headline": "Penguins win!", "location": "Pittsburgh",
+             "author": "Iceburgh", "content": "The Pittsburgh Penguins once again..."}
+        ]"#;
+
+        let items: Vec<Item> = serde_json::from_str(json).unwrap();
+
+        assert!(matches!(&items[0], Item::Tweet(tweet) if tweet.is_retweet()));
+        assert!(matches!(&items[1], Item::NewsArticle(_)));
+        assert_eq!(
+            "Penguins win!, by Iceburgh (Pittsburgh)",
+            items[1].summarize()
+        );
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let item = Item::Tweet(Tweet::new(1, "horse_ebooks", "of course").unwrap());
+
+        let json = serde_json::to_string(&item).unwrap();
+        let back: Item = serde_json::from_str(&json).unwrap();
+
+        assert!(json.starts_with(r#"{"type":"tweet","#));
+        assert_eq!(item.summarize(), back.summarize());
+    }
+
+    #[test]
+    fn over_long_tweet_is_an_error() {
+        let content = "a".repeat(crate::MAX_TWEET_CHARS + 1);
+        let json = format!(
+            r#"{{"type": "tweet", "id": 1, "username": "horse_ebooks", "content": "{content}"}}"#
+        );
+
+        let error = serde_json::from_str::<Item>(&json).unwrap_err();
+
+        assert!(
+            error.to_string().contains("at most 280 characters"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn unknown_type_is_an_error() {
+        let json = r#"{"type": "podcast", "host": "ferris"}"#;
+
+        assert!(serde_json::from_str::<Item>(json).is_err());
+    }
+}
@@ -1,13 +1,64 @@
-use std::fmt::{Debug, Display};
+use std::{
+    cmp,
+    fmt::{self, Debug, Display},
+    io,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub mod feed;
+pub mod item;
+pub mod notify;
+pub mod render;
+pub mod tags;
+pub mod thread;
+pub mod truncate;
+
+pub use feed::Feed;
+pub use item::Item;
+use render::Renderer;
+pub use thread::{Thread, ThreadBuilder};
 
 pub trait Summary {
-    fn summarize(&self) -> String;
+    fn summarize_author(&self) -> String;
+
+    // Default implementations can call other methods in the same trait, even
+    // if those other methods don't have a default implementation. A type only
+    // has to implement summarize_author to get summarize.
+    fn summarize(&self) -> String {
+        format!("(Read more from {}...)", self.summarize_author())
+    }
 
     fn with_default(&self) -> String {
         String::from("read more...")
     }
+
+    // The same summary for a page in another format. Types that don't say
+    // which parts to highlight get their plain summary, escaped.
+    fn render(&self, renderer: &dyn Renderer) -> String {
+        renderer.text(&self.summarize())
+    }
+
+    // The summary cut down to fit a preview, ending in "…" when it was cut.
+    fn summarize_within(&self, max_chars: usize) -> String {
+        truncate::truncate(&self.summarize(), max_chars)
+    }
+
+    // The words the item uses most. By default they come from the summary,
+    // but a type with more text of its own should look there instead.
+    fn keywords(&self) -> Vec<String> {
+        tags::keywords(&self.summarize(), tags::KEYWORD_COUNT)
+    }
+
+    // Topics the item was tagged with by its author, such as "#rust".
+    fn tags(&self) -> Vec<String> {
+        Vec::new()
+    }
 }
 
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NewsArticle {
     pub headline: String,
     pub location: String,
@@ -16,27 +67,187 @@ pub struct NewsArticle {
 }
 
 impl Summary for NewsArticle {
+    fn summarize_author(&self) -> String {
+        self.author.clone()
+    }
+
+    // Overriding the default: an article is summed up by its own headline.
     fn summarize(&self) -> String {
         format!("{}, by {} ({})", self.headline, self.author, self.location)
     }
+
+    fn keywords(&self) -> Vec<String> {
+        let text = format!("{}\n{}", self.headline, self.content);
+        tags::keywords(&text, tags::KEYWORD_COUNT)
+    }
+
+    fn render(&self, renderer: &dyn Renderer) -> String {
+        format!(
+            "{}, by {} ({})",
+            renderer.strong(&self.headline),
+            renderer.text(&self.author),
+            renderer.text(&self.location)
+        )
+    }
 }
 
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawTweet"))]
 pub struct Tweet {
+    pub id: u64,
     pub username: String,
     pub content: String,
-    pub reply: bool,
-    pub retweet: bool,
+    /// The tweet this one answers.
+    pub reply_to: Option<u64>,
+    /// The tweet this one shares.
+    pub retweet_of: Option<u64>,
+}
+
+// A tweet as read from JSON, before its length is checked. Going through
+// `Tweet::new` means a feed can't load a tweet that would be refused here.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawTweet {
+    id: u64,
+    username: String,
+    content: String,
+    reply_to: Option<u64>,
+    retweet_of: Option<u64>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawTweet> for Tweet {
+    type Error = TweetError;
+
+    fn try_from(raw: RawTweet) -> Result<Tweet, TweetError> {
+        let tweet = Tweet::new(raw.id, &raw.username, &raw.content)?;
+
+        Ok(Tweet {
+            reply_to: raw.reply_to,
+            retweet_of: raw.retweet_of,
+            ..tweet
+        })
+    }
+}
+
+/// The most characters a tweet's content may have.
+pub const MAX_TWEET_CHARS: usize = 280;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TweetError {
+    TooLong { chars: usize, max: usize },
+}
+
+impl Display for TweetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TweetError::TooLong { chars, max } => {
+                write!(
+                    f,
+                    "a tweet can have at most {max} characters, this one has {chars}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for TweetError {}
+
+impl Tweet {
+    /// A new tweet that is neither a reply nor a retweet, as long as its
+    /// content fits in `MAX_TWEET_CHARS`.
+    pub fn new(id: u64, username: &str, content: &str) -> Result<Tweet, TweetError> {
+        let chars = truncate::length(content);
+        if chars > MAX_TWEET_CHARS {
+            return Err(TweetError::TooLong {
+                chars,
+                max: MAX_TWEET_CHARS,
+            });
+        }
+
+        Ok(Tweet {
+            id,
+            username: username.to_string(),
+            content: content.to_string(),
+            reply_to: None,
+            retweet_of: None,
+        })
+    }
+
+    /// Makes this tweet a reply to the tweet with ID `parent`.
+    pub fn replying_to(self, parent: u64) -> Tweet {
+        Tweet {
+            reply_to: Some(parent),
+            retweet_of: None,
+            ..self
+        }
+    }
+
+    /// Makes this tweet a retweet of the tweet with ID `original`.
+    pub fn retweeting(self, original: u64) -> Tweet {
+        Tweet {
+            reply_to: None,
+            retweet_of: Some(original),
+            ..self
+        }
+    }
+
+    pub fn is_reply(&self) -> bool {
+        self.reply_to.is_some()
+    }
+
+    pub fn is_retweet(&self) -> bool {
+        self.retweet_of.is_some()
+    }
+
+    /// The tweet this one replies to or retweets, if any.
+    pub fn parent(&self) -> Option<u64> {
+        self.reply_to.or(self.retweet_of)
+    }
 }
 
 impl Summary for Tweet {
+    fn summarize_author(&self) -> String {
+        format!("@{}", self.username)
+    }
+
     fn summarize(&self) -> String {
         format!("{}: {}", self.username, self.content)
     }
+
+    fn keywords(&self) -> Vec<String> {
+        tags::keywords(&self.content, tags::KEYWORD_COUNT)
+    }
+
+    fn tags(&self) -> Vec<String> {
+        let mut tags = tags::hashtags(&self.content);
+        tags.extend(tags::mentions(&self.content));
+        tags
+    }
+
+    fn render(&self, renderer: &dyn Renderer) -> String {
+        format!(
+            "{}: {}",
+            renderer.strong(&self.username),
+            renderer.text(&self.content)
+        )
+    }
 }
 
 impl Display for Tweet {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "reply: {}, retweet: {}", self.reply, self.retweet)
+        match (self.reply_to, self.retweet_of) {
+            (_, Some(original)) => {
+                write!(f, "#{} @{} retweeted #{original}", self.id, self.username)
+            }
+            (Some(parent), _) => write!(
+                f,
+                "#{} @{}: {} (reply to #{parent})",
+                self.id, self.username, self.content
+            ),
+            (None, None) => write!(f, "#{} @{}: {}", self.id, self.username, self.content),
+        }
     }
 }
 
@@ -53,10 +264,12 @@ pub fn notify2<T: Summary>(item: &T) {
 
 // Using impl Trait is appropriate if wen this function to allow item1 and item2
 // to have different type (as long as both types implement Summary).
+#[allow(unused_variables)]
 pub fn notify3(item1: &impl Summary, item2: &impl Summary) {}
 
 // If we want to force both parameters to have the same type, however, we must
 // use a trait bound, like this:
+#[allow(unused_variables)]
 pub fn notify4<T: Summary>(item1: &T, item2: &T) {}
 
 // Specifying Multiple Trait Bounds with the + Syntax
@@ -67,6 +280,7 @@ pub fn notify5(item: &(impl Summary + Display)) {
 }
 
 // The + syntax is also valid with trait bounds on generic types:
+#[allow(unused_variables)]
 pub fn notify6<T: Summary + Display>(item: &T) {}
 
 // Clearer Trait Bounds with where Clauses
@@ -76,9 +290,13 @@ pub fn notify6<T: Summary + Display>(item: &T) {}
 // signature hard to read. For this reason, Rust has alternative syntax for specifying
 // trait bounds inside a where clause after the function signature. So intead of
 // writing this:
-fn some_function<T: Display + Clone, U: Clone + Debug>(t: &T, u: &U) -> i32 {}
+#[allow(unused_variables, dead_code)]
+fn some_function<T: Display + Clone, U: Clone + Debug>(t: &T, u: &U) -> i32 {
+    8
+}
 
 // we can use a where caluse, like this:
+#[allow(unused_variables, dead_code)]
 fn some_function2<T, U>(t: &T, u: &U) -> i32
 where
     T: Display + Clone,
@@ -100,12 +318,14 @@ where
 // the Iterator trait without needing to write out a very long type.
 
 // However, you can only use impl Trait if you're returning a single type.
+#[allow(dead_code)]
 fn returns_summarizable() -> impl Summary {
     Tweet {
+        id: 1,
         username: "horse_ebook".to_string(),
         content: String::from("of course, as you probably already know, people"),
-        reply: false,
-        retweet: false,
+        reply_to: None,
+        retweet_of: None,
     }
 }
 
@@ -113,25 +333,76 @@ fn returns_summarizable() -> impl Summary {
 // By using a trait bound with an impl block that uses generic type parameters,
 // we can implement methods conditionally for types that implement the specified
 // traits.
-struct Pair<T> {
-    x: T,
-    y: T,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pair<T> {
+    pub x: T,
+    pub y: T,
 }
 
 impl<T> Pair<T> {
-    fn new(x: T, y: T) -> Self {
+    pub fn new(x: T, y: T) -> Self {
         Self { x, y }
     }
 }
 
+// Only types that can be compared get largest and smallest. When x and y are
+// equal, x is the one returned.
+impl<T: PartialOrd> Pair<T> {
+    pub fn largest(&self) -> &T {
+        if self.x >= self.y {
+            &self.x
+        } else {
+            &self.y
+        }
+    }
+
+    pub fn smallest(&self) -> &T {
+        if self.x <= self.y {
+            &self.x
+        } else {
+            &self.y
+        }
+    }
+}
+
+// And only types that can also be displayed get cmp_display. It writes to a
+// String or a Formatter through fmt::Write, and to stdout or a file through
+// io::Write. One method can't take both, since a type could implement both
+// traits and Rust wouldn't know which write to use.
 impl<T: Display + PartialOrd> Pair<T> {
-    fn cmd_display(&self) {
+    pub fn cmp_display(&self, out: &mut impl fmt::Write) -> fmt::Result {
         if self.x >= self.y {
-            println!("The largest member is x = {}", self.x);
+            writeln!(out, "The largest member is x = {}", self.x)
         } else {
-            println!("The largest member is y = {}", self.y);
+            writeln!(out, "The largest member is y = {}", self.y)
         }
     }
+
+    pub fn cmp_display_io(&self, out: &mut impl io::Write) -> io::Result<()> {
+        let mut line = String::new();
+        self.cmp_display(&mut line).map_err(io::Error::other)?;
+
+        out.write_all(line.as_bytes())
+    }
+}
+
+// With a total order, the pair can be taken apart into its larger or smaller
+// member. Like std::cmp::max and min, max returns y and min returns x when
+// they are equal.
+impl<T: Ord> Pair<T> {
+    pub fn max(self) -> T {
+        cmp::max(self.x, self.y)
+    }
+
+    pub fn min(self) -> T {
+        cmp::min(self.x, self.y)
+    }
+}
+
+impl<T> From<(T, T)> for Pair<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Pair::new(x, y)
+    }
 }
 
 // We can also conditionally implement a trait for any type that implements another
@@ -146,3 +417,153 @@ impl<T: Display + PartialOrd> Pair<T> {
 // Display trait, we can turn integers into their corresponding String values like
 // this because integers implement Display:
 // let s = 3.to_string();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Implements only the required method, so it gets the default summarize.
+    struct Podcast {
+        host: String,
+    }
+
+    impl Summary for Podcast {
+        fn summarize_author(&self) -> String {
+            self.host.clone()
+        }
+    }
+
+    #[test]
+    fn default_summarize_uses_the_author() {
+        let podcast = Podcast {
+            host: String::from("ferris"),
+        };
+
+        assert_eq!("(Read more from ferris...)", podcast.summarize());
+    }
+
+    #[test]
+    fn overrides_replace_the_default() {
+        let article = NewsArticle {
+            headline: String::from("Penguins win the Stanley Cup Championship!"),
+            location: String::from("Pittsburgh, PA, USA"),
+            author: String::from("Iceburgh"),
+            content: String::new(),
+        };
+        let tweet = Tweet {
+            id: 1,
+            username: String::from("horse_ebooks"),
+            content: String::from("of course"),
+            reply_to: None,
+            retweet_of: None,
+        };
+
+        assert_eq!("Iceburgh", article.summarize_author());
+        assert_eq!(
+            "Penguins win the Stanley Cup Championship!, by Iceburgh (Pittsburgh, PA, USA)",
+            article.summarize()
+        );
+        assert_eq!("@horse_ebooks", tweet.summarize_author());
+        assert_eq!("horse_ebooks: of course", tweet.summarize());
+    }
+
+    #[test]
+    fn pair_finds_largest_and_smallest() {
+        let pair = Pair::new(3, 8);
+
+        assert_eq!(&8, pair.largest());
+        assert_eq!(&3, pair.smallest());
+        assert_eq!(8, pair.max());
+        assert_eq!(3, pair.min());
+
+        // Only PartialOrd is needed for references, so floats work too.
+        let floats = Pair::new(2.5, -1.0);
+        assert_eq!(&2.5, floats.largest());
+        assert_eq!(&-1.0, floats.smallest());
+    }
+
+    // Compares by rank only, so two cards can be equal but still be told
+    // apart by their name.
+    #[derive(Debug)]
+    struct Card(u8, &'static str);
+
+    impl PartialEq for Card {
+        fn eq(&self, other: &Card) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl Eq for Card {}
+
+    impl PartialOrd for Card {
+        fn partial_cmp(&self, other: &Card) -> Option<cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Card {
+        fn cmp(&self, other: &Card) -> cmp::Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    #[test]
+    fn pair_ties() {
+        let pair = || Pair::new(Card(7, "x"), Card(7, "y"));
+
+        assert_eq!("x", pair().largest().1);
+        assert_eq!("x", pair().smallest().1);
+        assert_eq!("y", pair().max().1);
+        assert_eq!("x", pair().min().1);
+    }
+
+    #[test]
+    fn pair_from_tuple() {
+        assert_eq!(Pair::new("a", "b"), Pair::from(("a", "b")));
+
+        let pair: Pair<char> = ('x', 'y').into();
+        assert_eq!(&'y', pair.largest());
+    }
+
+    #[test]
+    fn cmp_display_writes_to_strings_and_io() {
+        let mut text = String::new();
+        Pair::new(10, 4).cmp_display(&mut text).unwrap();
+        Pair::new(1, 4).cmp_display(&mut text).unwrap();
+
+        let mut bytes = Vec::new();
+        Pair::new("b", "a").cmp_display_io(&mut bytes).unwrap();
+
+        assert_eq!(
+            "The largest member is x = 10\nThe largest member is y = 4\n",
+            text
+        );
+        assert_eq!(
+            "The largest member is x = b\n",
+            String::from_utf8(bytes).unwrap()
+        );
+    }
+
+    #[test]
+    fn summarize_within_truncates_the_summary() {
+        let tweet =
+            Tweet::new(1, "horse_ebooks", "of course, as you probably already know").unwrap();
+
+        assert_eq!("horse_ebooks: of course, as…", tweet.summarize_within(28));
+        assert_eq!(tweet.summarize(), tweet.summarize_within(100));
+    }
+
+    #[test]
+    fn tweets_have_a_length_limit() {
+        let longest = "🦀".repeat(MAX_TWEET_CHARS);
+
+        assert!(Tweet::new(1, "ferris", &longest).is_ok());
+        assert_eq!(
+            TweetError::TooLong {
+                chars: MAX_TWEET_CHARS + 1,
+                max: MAX_TWEET_CHARS
+            },
+            Tweet::new(1, "ferris", &format!("{longest}!")).unwrap_err()
+        );
+    }
+}
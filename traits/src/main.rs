@@ -1,4 +1,6 @@
-use traits::{notify, notify5, NewsArticle, Summary, Tweet};
+use std::time::SystemTime;
+
+use traits::{notify, notify5, Feed, NewsArticle, Summary, Tweet};
 
 fn main() {
     // Traits: Defining shared behavior
@@ -10,6 +12,35 @@ fn main() {
     defining_trait();
 
     trait_as_parameters();
+
+    mixed_feed();
+}
+
+fn mixed_feed() {
+    // A Feed stores Box<dyn Summary>, so tweets and articles can share it.
+    let mut feed = Feed::new();
+
+    feed.push(
+        SystemTime::now(),
+        Tweet {
+            id: 1,
+            username: String::from("horse_ebooks"),
+            content: String::from("of course, as you probably already known, people"),
+            reply_to: None,
+            retweet_of: None,
+        },
+    );
+    feed.push(
+        SystemTime::now(),
+        NewsArticle {
+            headline: "Headline".to_string(),
+            content: "Content".to_string(),
+            author: "Author".to_string(),
+            location: "Location".to_string(),
+        },
+    );
+
+    println!("{}", feed.digest(5));
 }
 
 fn trait_as_parameters() {
@@ -29,10 +60,11 @@ fn defining_trait() {
     // types. Trait definitions are a way to group method signatures together to
     // define a set of behavios necessary to accomplish some purpose.
     let tweet = Tweet {
+        id: 1,
         username: String::from("horse_ebooks"),
         content: String::from("of course, as you probably already known, people"),
-        reply: false,
-        retweet: false,
+        reply_to: None,
+        retweet_of: None,
     };
 
     println!("1 new tweet: {}", tweet.summarize());
@@ -0,0 +1,327 @@
+// Where breaking news goes. Each channel implements Notifier, and a
+// Dispatcher sends one item to all of them, trying each channel again a few
+// times before reporting that it failed.
+
+use std::{
+    fs::OpenOptions,
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpStream, ToSocketAddrs},
+    path::PathBuf,
+    thread,
+    time::Duration,
+};
+
+use crate::Summary;
+
+/// How long the webhook waits to connect, write or read before giving up.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub trait Notifier {
+    /// Names the channel in delivery reports.
+    fn name(&self) -> String;
+
+    fn send(&mut self, message: &str) -> io::Result<()>;
+}
+
+/// Prints each message on its own line.
+pub struct Stdout;
+
+impl Notifier for Stdout {
+    fn name(&self) -> String {
+        String::from("stdout")
+    }
+
+    fn send(&mut self, message: &str) -> io::Result<()> {
+        writeln!(io::stdout().lock(), "{message}")
+    }
+}
+
+/// Adds each message as a line at the end of a file, creating it if needed.
+/// Nothing already in the file is ever changed.
+pub struct AppendFile {
+    path: PathBuf,
+}
+
+impl AppendFile {
+    pub fn new(path: impl Into<PathBuf>) -> AppendFile {
+        AppendFile { path: path.into() }
+    }
+}
+
+impl Notifier for AppendFile {
+    fn name(&self) -> String {
+        format!("file {}", self.path.display())
+    }
+
+    fn send(&mut self, message: &str) -> io::Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+
+        writeln!(file, "{message}")
+    }
+}
+
+/// POSTs each message as plain text to a web hook on this machine, such as
+/// `http://localhost:8080/news`. It stands in for a real web hook service,
+/// so only local addresses are allowed.
+pub struct Webhook {
+    host: String,
+    port: u16,
+    path: String,
+}
+
+impl Webhook {
+    pub fn new(url: &str) -> Result<Webhook, &'static str> {
+        let rest = url
+            .strip_prefix("http://")
+            .ok_or("the web hook URL must start with http://")?;
+
+        let (authority, path) = match rest.find('/') {
+            Some(slash) => rest.split_at(slash),
+            None => (rest, "/"),
+        };
+
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) if !port.ends_with(']') => (
+                host,
+                port.parse()
+                    .map_err(|_| "the web hook port must be a number")?,
+            ),
+            _ => (authority, 80),
+        };
+
+        if !matches!(host, "localhost" | "127.0.0.1" | "[::1]") {
+            return Err("the web hook must be on localhost");
+        }
+
+        Ok(Webhook {
+            host: host.to_string(),
+            port,
+            path: path.to_string(),
+        })
+    }
+}
+
+impl Notifier for Webhook {
+    fn name(&self) -> String {
+        format!("webhook http://{}:{}{}", self.host, self.port, self.path)
+    }
+
+    fn send(&mut self, message: &str) -> io::Result<()> {
+        let host = self.host.trim_start_matches('[').trim_end_matches(']');
+        let addr = (host, self.port)
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| io::Error::other("the web hook host has no address"))?;
+
+        let mut stream = TcpStream::connect_timeout(&addr, WEBHOOK_TIMEOUT)?;
+        stream.set_read_timeout(Some(WEBHOOK_TIMEOUT))?;
+        stream.set_write_timeout(Some(WEBHOOK_TIMEOUT))?;
+
+        write!(
+            stream,
+            "POST {} HTTP/1.1\r\n\
+             Host: {}:{}\r\n\
+             Content-Type: text/plain; charset=utf-8\r\n\
+             Content-Length: {}\r\n\
+             Connection: close\r\n\
+             \r\n\
+             {message}",
+            self.path,
+            self.host,
+            self.port,
+            message.len()
+        )?;
+
+        // Only the status line matters, e.g. "HTTP/1.1 204 No Content".
+        let mut status_line = String::new();
+        BufReader::new(stream).read_line(&mut status_line)?;
+
+        match status_line.split_whitespace().nth(1) {
+            Some(status) if status.starts_with('2') => Ok(()),
+            Some(status) => Err(io::Error::other(format!(
+                "the web hook answered with status {status}"
+            ))),
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the web hook didn't answer with HTTP",
+            )),
+        }
+    }
+}
+
+/// What happened when one channel was sent a message.
+#[derive(Debug)]
+pub struct Delivery {
+    pub channel: String,
+    /// How many times sending was tried, including the first time.
+    pub attempts: u32,
+    /// The error from the last attempt if none of them worked.
+    pub result: io::Result<()>,
+}
+
+impl Delivery {
+    pub fn is_ok(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+/// Sends each message to every channel.
+pub struct Dispatcher {
+    channels: Vec<Box<dyn Notifier>>,
+    retries: u32,
+    retry_delay: Duration,
+}
+
+impl Dispatcher {
+    /// A dispatcher that tries a failing channel `retries` more times,
+    /// waiting `retry_delay` before each new try.
+    pub fn new(retries: u32, retry_delay: Duration) -> Dispatcher {
+        Dispatcher {
+            channels: Vec::new(),
+            retries,
+            retry_delay,
+        }
+    }
+
+    pub fn add(&mut self, channel: impl Notifier + 'static) {
+        self.channels.push(Box::new(channel));
+    }
+
+    /// Sends "Breaking news!" with the item's summary to every channel. One
+    /// failing channel doesn't stop the others, so there is a report for each.
+    pub fn notify(&mut self, item: &dyn Summary) -> Vec<Delivery> {
+        let message = format!("Breaking news! {}", item.summarize());
+
+        self.channels
+            .iter_mut()
+            .map(|channel| {
+                let mut attempts = 0;
+
+                let result = loop {
+                    attempts += 1;
+
+                    match channel.send(&message) {
+                        Ok(()) => break Ok(()),
+                        Err(e) if attempts > self.retries => break Err(e),
+                        Err(_) => thread::sleep(self.retry_delay),
+                    }
+                };
+
+                Delivery {
+                    channel: channel.name(),
+                    attempts,
+                    result,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Tweet;
+    use std::{fs, io::Read, net::TcpListener};
+
+    /// Fails a set number of times before it starts working.
+    struct Flaky {
+        failures_left: u32,
+        sent: Vec<String>,
+    }
+
+    impl Notifier for Flaky {
+        fn name(&self) -> String {
+            String::from("flaky")
+        }
+
+        fn send(&mut self, message: &str) -> io::Result<()> {
+            if self.failures_left > 0 {
+                self.failures_left -= 1;
+                return Err(io::Error::other("not now"));
+            }
+
+            self.sent.push(message.to_string());
+            Ok(())
+        }
+    }
+
+    fn tweet() -> Tweet {
+        Tweet::new(1, "horse_ebooks", "of course").unwrap()
+    }
+
+    #[test]
+    fn retries_each_channel_and_reports_it() {
+        let mut dispatcher = Dispatcher::new(2, Duration::ZERO);
+        dispatcher.add(Flaky {
+            failures_left: 2,
+            sent: Vec::new(),
+        });
+        dispatcher.add(Flaky {
+            failures_left: 5,
+            sent: Vec::new(),
+        });
+
+        let deliveries = dispatcher.notify(&tweet());
+
+        assert!(deliveries[0].is_ok());
+        assert_eq!(3, deliveries[0].attempts);
+        assert!(!deliveries[1].is_ok());
+        assert_eq!(3, deliveries[1].attempts);
+    }
+
+    #[test]
+    fn file_channel_appends_lines() {
+        let path = std::env::temp_dir().join(format!("news-{}.txt", std::process::id()));
+        let mut file = AppendFile::new(&path);
+
+        file.send("first").unwrap();
+        file.send("second").unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!("first\nsecond\n", contents);
+    }
+
+    #[test]
+    fn webhook_posts_the_message() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+
+            // The body ends the request, so read until it has arrived.
+            while !request.ends_with(b"of course") {
+                let n = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            stream
+                .write_all(b"HTTP/1.1 204 No Content\r\n\r\n")
+                .unwrap();
+
+            String::from_utf8(request).unwrap()
+        });
+
+        let mut dispatcher = Dispatcher::new(0, Duration::ZERO);
+        dispatcher.add(Webhook::new(&format!("http://127.0.0.1:{port}/news")).unwrap());
+        let deliveries = dispatcher.notify(&tweet());
+        let request = server.join().unwrap();
+
+        assert!(deliveries[0].is_ok(), "{:?}", deliveries[0].result);
+        assert!(request.starts_with("POST /news HTTP/1.1\r\n"));
+        assert!(request.ends_with("\r\n\r\nBreaking news! horse_ebooks: of course"));
+    }
+
+    #[test]
+    fn webhook_only_allows_localhost() {
+        assert!(Webhook::new("http://localhost:8080/news").is_ok());
+        assert!(Webhook::new("http://[::1]:8080").is_ok());
+        assert!(Webhook::new("http://example.com/news").is_err());
+        assert!(Webhook::new("https://localhost/news").is_err());
+    }
+}
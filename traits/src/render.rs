@@ -0,0 +1,154 @@
+// Renderers turn the parts of a summary into one output format. The item
+// decides which parts to highlight (see `Summary::render`), the renderer
+// decides how, and escapes everything that came from users on the way.
+
+pub trait Renderer {
+    /// Text shown as written, escaped for the format.
+    fn text(&self, text: &str) -> String;
+
+    /// Text that stands out, such as a headline or a user name.
+    fn strong(&self, text: &str) -> String;
+}
+
+pub struct PlainText;
+
+impl Renderer for PlainText {
+    fn text(&self, text: &str) -> String {
+        text.to_string()
+    }
+
+    fn strong(&self, text: &str) -> String {
+        text.to_string()
+    }
+}
+
+pub struct Markdown;
+
+impl Renderer for Markdown {
+    // A summary is a single line, so line breaks become spaces, a `#` can't
+    // start a heading, a `-`, `+` or `1.` at the start can't start a list,
+    // and an `&` can't turn what follows into an entity like `&lt;`.
+    fn text(&self, text: &str) -> String {
+        let mut escaped = String::with_capacity(text.len());
+
+        let indent = text.len() - text.trim_start().len();
+        let digits = text[indent..]
+            .bytes()
+            .take_while(u8::is_ascii_digit)
+            .count();
+        let list_marker = match text.as_bytes().get(indent + digits) {
+            Some(b'-' | b'+') if digits == 0 => Some(indent),
+            Some(b'.' | b')') if digits > 0 => Some(indent + digits),
+            _ => None,
+        };
+
+        for (i, c) in text.char_indices() {
+            if Some(i) == list_marker {
+                escaped.push('\\');
+            }
+
+            match c {
+                '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '!' | '|' | '~' | '&' => {
+                    escaped.push('\\');
+                    escaped.push(c);
+                }
+                '\n' | '\r' => escaped.push(' '),
+                _ => escaped.push(c),
+            }
+        }
+
+        escaped
+    }
+
+    fn strong(&self, text: &str) -> String {
+        format!("**{}**", self.text(text))
+    }
+}
+
+pub struct Html;
+
+impl Renderer for Html {
+    fn text(&self, text: &str) -> String {
+        let mut escaped = String::with_capacity(text.len());
+
+        for c in text.chars() {
+            match c {
+                '&' => escaped.push_str("&amp;"),
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '"' => escaped.push_str("&quot;"),
+                '\'' => escaped.push_str("&#39;"),
+                _ => escaped.push(c),
+            }
+        }
+
+        escaped
+    }
+
+    fn strong(&self, text: &str) -> String {
+        format!("<strong>{}</strong>", self.text(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NewsArticle, Summary, Tweet};
+
+    fn tweet(content: &str) -> Tweet {
+        Tweet {
+            id: 1,
+            username: String::from("horse_ebooks"),
+            content: content.to_string(),
+            reply_to: None,
+            retweet_of: None,
+        }
+    }
+
+    #[test]
+    fn plain_text_matches_summarize() {
+        let tweet = tweet("of course, as you probably already know, people");
+
+        assert_eq!(tweet.summarize(), tweet.render(&PlainText));
+    }
+
+    #[test]
+    fn markdown_escapes_content() {
+        let tweet = tweet("*not bold* [link](x)\n# not a heading &lt;b&gt;");
+
+        assert_eq!(
+            "**horse\\_ebooks**: \\*not bold\\* \\[link\\](x) \\# not a heading \\&lt;b\\&gt;",
+            tweet.render(&Markdown)
+        );
+    }
+
+    #[test]
+    fn markdown_escapes_list_markers_at_the_start() {
+        assert_eq!(
+            "\\- not a list - really",
+            Markdown.text("- not a list - really")
+        );
+        assert_eq!("  \\+ nor this", Markdown.text("  + nor this"));
+        assert_eq!(
+            "1984\\. was a year, 2. too",
+            Markdown.text("1984. was a year, 2. too")
+        );
+        assert_eq!("3\\) no", Markdown.text("3) no"));
+        assert_eq!("1984 was a year", Markdown.text("1984 was a year"));
+    }
+
+    #[test]
+    fn html_escapes_content() {
+        let article = NewsArticle {
+            headline: String::from("Tom & Jerry <3"),
+            location: String::from("\"Here\""),
+            author: String::from("O'Brien"),
+            content: String::new(),
+        };
+
+        assert_eq!(
+            "<strong>Tom &amp; Jerry &lt;3</strong>, by O&#39;Brien (&quot;Here&quot;)",
+            article.render(&Html)
+        );
+    }
+}
@@ -0,0 +1,108 @@
+// Finding what an item is about: the words it uses most, and for tweets the
+// #hashtags and @mentions written into the content.
+
+use std::collections::HashMap;
+
+/// How many keywords `Summary::keywords` picks.
+pub const KEYWORD_COUNT: usize = 5;
+
+/// Words too common to say anything about a text.
+const STOPWORDS: &[&str] = &[
+    "a", "about", "after", "all", "also", "an", "and", "any", "are", "as", "at", "be", "been",
+    "but", "by", "can", "could", "did", "do", "does", "for", "from", "had", "has", "have", "he",
+    "her", "his", "how", "i", "if", "in", "into", "is", "it", "its", "just", "know", "more", "my",
+    "no", "not", "of", "on", "once", "one", "or", "our", "out", "she", "so", "some", "than",
+    "that", "the", "their", "them", "then", "there", "they", "this", "to", "up", "us", "was", "we",
+    "were", "what", "when", "which", "who", "will", "with", "would", "you", "your",
+];
+
+/// The `n` words used most in `text`, lowercased, leaving out stopwords and
+/// anything shorter than two letters. Words used equally often come in the
+/// order they first appear.
+pub fn keywords(text: &str, n: usize) -> Vec<String> {
+    let mut counts: HashMap<String, (usize, usize)> = HashMap::new();
+
+    let words = text
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .map(|word| word.trim_matches('\'').to_lowercase())
+        .filter(|word| word.chars().count() >= 2 && !STOPWORDS.contains(&word.as_str()));
+
+    for (position, word) in words.enumerate() {
+        counts.entry(word).or_insert((0, position)).0 += 1;
+    }
+
+    let mut counts: Vec<(String, (usize, usize))> = counts.into_iter().collect();
+    counts.sort_by_key(|(_, (count, first))| (std::cmp::Reverse(*count), *first));
+
+    counts.into_iter().take(n).map(|(word, _)| word).collect()
+}
+
+/// Words in `text` that start with `sigil`, such as `#rust` or `@ferris`,
+/// each once in the order they appear. A tag is letters, digits and `_`.
+fn prefixed(text: &str, sigil: char) -> Vec<String> {
+    let mut found: Vec<String> = Vec::new();
+
+    for word in text.split_whitespace() {
+        let Some(rest) = word.strip_prefix(sigil) else {
+            continue;
+        };
+        let name: String = rest
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect();
+
+        if !name.is_empty() {
+            let tag = format!("{sigil}{name}");
+            if !found.contains(&tag) {
+                found.push(tag);
+            }
+        }
+    }
+
+    found
+}
+
+/// The hashtags in `text`, lowercased since `#Rust` and `#rust` are the
+/// same topic.
+pub fn hashtags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+
+    for tag in prefixed(text, '#') {
+        let tag = tag.to_lowercase();
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+
+    tags
+}
+
+/// The users mentioned in `text`, as written.
+pub fn mentions(text: &str) -> Vec<String> {
+    prefixed(text, '@')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keywords_are_the_most_used_words() {
+        let text = "The Penguins won. The Penguins once again are the best hockey team, \
+                    and hockey fans in Pittsburgh love the Penguins.";
+
+        assert_eq!(
+            vec!["penguins", "hockey", "won", "again"],
+            keywords(text, 4)
+        );
+    }
+
+    #[test]
+    fn finds_hashtags_and_mentions() {
+        let text = "Thanks @ferris! #Rust 1.0 is out. #rust #100DaysOfCode, cc @bors.";
+
+        assert_eq!(vec!["#rust", "#100daysofcode"], hashtags(text));
+        assert_eq!(vec!["@ferris", "@bors"], mentions(text));
+        assert!(hashtags("# not a tag, nor is a#b").is_empty());
+    }
+}
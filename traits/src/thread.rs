@@ -0,0 +1,250 @@
+// Conversations: every reply and retweet points at its parent by ID, and the
+// builder turns a pile of tweets into trees with each parent above the
+// tweets that answer or share it.
+
+use std::{collections::BTreeMap, fmt};
+
+use crate::Tweet;
+
+/// One tweet and everything posted in answer to it, oldest first.
+#[derive(Debug)]
+pub struct Thread {
+    pub tweet: Tweet,
+    pub replies: Vec<Thread>,
+}
+
+impl Thread {
+    /// How many tweets the thread holds, this one included.
+    pub fn tweet_count(&self) -> usize {
+        let mut count = 0;
+        let mut pending = vec![self];
+
+        while let Some(thread) = pending.pop() {
+            count += 1;
+            pending.extend(&thread.replies);
+        }
+
+        count
+    }
+}
+
+// Threads can nest as deep as a conversation goes, so everything that walks
+// them keeps its own stack instead of recursing once per reply.
+impl Drop for Thread {
+    fn drop(&mut self) {
+        let mut pending = std::mem::take(&mut self.replies);
+
+        while let Some(mut thread) = pending.pop() {
+            pending.append(&mut thread.replies);
+        }
+    }
+}
+
+// Each reply is indented two spaces more than the tweet it answers:
+//
+//   @horse_ebooks: of course
+//     @ferris: as you probably already know
+//       @bors retweeted
+impl fmt::Display for Thread {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut pending = vec![(self, 0)];
+
+        while let Some((thread, depth)) = pending.pop() {
+            let indent = "  ".repeat(depth);
+            let tweet = &thread.tweet;
+
+            // Indenting already shows what each tweet answers, so only
+            // retweets need saying.
+            if tweet.is_retweet() {
+                writeln!(f, "{indent}@{} retweeted", tweet.username)?;
+            } else {
+                writeln!(f, "{indent}@{}: {}", tweet.username, tweet.content)?;
+            }
+
+            // Pushed newest first so the oldest reply is written next.
+            pending.extend(thread.replies.iter().rev().map(|reply| (reply, depth + 1)));
+        }
+
+        Ok(())
+    }
+}
+
+/// Collects tweets in any order and assembles them into threads.
+#[derive(Debug, Default)]
+pub struct ThreadBuilder {
+    tweets: BTreeMap<u64, Tweet>,
+}
+
+impl ThreadBuilder {
+    pub fn new() -> ThreadBuilder {
+        ThreadBuilder::default()
+    }
+
+    /// Adds a tweet. A tweet with the same ID as one added before replaces it.
+    pub fn add(&mut self, tweet: Tweet) -> &mut ThreadBuilder {
+        self.tweets.insert(tweet.id, tweet);
+        self
+    }
+
+    /// The conversation trees, in ID order. A tweet whose parent wasn't added
+    /// starts a thread of its own, and so does a tweet caught in a loop of
+    /// replies, so no tweet is ever lost.
+    pub fn build(mut self) -> Vec<Thread> {
+        let mut children: BTreeMap<u64, Vec<u64>> = BTreeMap::new();
+        let mut roots = Vec::new();
+
+        for tweet in self.tweets.values() {
+            match tweet.parent() {
+                Some(parent) if parent != tweet.id && self.tweets.contains_key(&parent) => {
+                    children.entry(parent).or_default().push(tweet.id)
+                }
+                _ => roots.push(tweet.id),
+            }
+        }
+
+        let mut threads: Vec<Thread> = roots
+            .into_iter()
+            .map(|id| self.take(id, &children))
+            .collect();
+
+        // Whatever is left only answers itself in a circle.
+        while let Some(id) = self.tweets.first_key_value().map(|(id, _)| *id) {
+            threads.push(self.take(id, &children));
+        }
+        threads.sort_by_key(|thread| thread.tweet.id);
+
+        threads
+    }
+
+    // Moves the tweet and its replies out of the builder, so a tweet that
+    // has already been placed in a thread is never placed again. Each entry
+    // on the stack is a thread still collecting its replies, with the index
+    // of the next child to look at.
+    fn take(&mut self, id: u64, children: &BTreeMap<u64, Vec<u64>>) -> Thread {
+        let no_children = Vec::new();
+        let open = |tweet: Tweet| {
+            let ids = children.get(&tweet.id).unwrap_or(&no_children);
+            (
+                Thread {
+                    tweet,
+                    replies: Vec::new(),
+                },
+                ids,
+                0,
+            )
+        };
+
+        let tweet = self.tweets.remove(&id).expect("tweet not placed yet");
+        let mut stack = vec![open(tweet)];
+
+        loop {
+            let (_, ids, next) = stack.last_mut().expect("stack holds the root");
+
+            if let Some(&child) = ids.get(*next) {
+                *next += 1;
+                if let Some(tweet) = self.tweets.remove(&child) {
+                    stack.push(open(tweet));
+                }
+                continue;
+            }
+
+            let (thread, _, _) = stack.pop().expect("stack holds the root");
+            match stack.last_mut() {
+                Some((parent, _, _)) => parent.replies.push(thread),
+                None => return thread,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tweet(id: u64, username: &str, content: &str) -> Tweet {
+        Tweet::new(id, username, content).unwrap()
+    }
+
+    #[test]
+    fn builds_and_indents_a_conversation() {
+        let mut builder = ThreadBuilder::new();
+        builder
+            .add(tweet(3, "bors", "").retweeting(2))
+            .add(tweet(1, "horse_ebooks", "of course"))
+            .add(tweet(4, "ferris", "indeed").replying_to(1))
+            .add(tweet(2, "ferris", "as you probably already know").replying_to(1))
+            .add(tweet(5, "rustacean", "unrelated"));
+
+        let threads = builder.build();
+
+        assert_eq!(2, threads.len());
+        assert_eq!(4, threads[0].tweet_count());
+        assert_eq!(
+            "@horse_ebooks: of course\n\
+             \x20 @ferris: as you probably already know\n\
+             \x20   @bors retweeted\n\
+             \x20 @ferris: indeed\n",
+            threads[0].to_string()
+        );
+        assert_eq!("@rustacean: unrelated\n", threads[1].to_string());
+    }
+
+    #[test]
+    fn missing_parents_and_loops_start_their_own_threads() {
+        let mut builder = ThreadBuilder::new();
+        builder
+            .add(tweet(7, "ferris", "replying to a deleted tweet").replying_to(6))
+            .add(tweet(8, "a", "ping").replying_to(9))
+            .add(tweet(9, "b", "pong").replying_to(8));
+
+        let threads = builder.build();
+        let total: usize = threads.iter().map(Thread::tweet_count).sum();
+
+        assert_eq!(3, total);
+        assert_eq!(7, threads[0].tweet.id);
+    }
+
+    #[test]
+    fn deep_reply_chains_do_not_overflow_the_stack() {
+        const DEPTH: u64 = 20_000;
+
+        // Counts lines instead of keeping them, since the indenting alone
+        // adds up to hundreds of megabytes.
+        struct Lines(u64);
+
+        impl fmt::Write for Lines {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                self.0 += s.matches('\n').count() as u64;
+                Ok(())
+            }
+        }
+
+        let mut builder = ThreadBuilder::new();
+        builder.add(tweet(0, "ferris", "first"));
+        for id in 1..DEPTH {
+            builder.add(tweet(id, "ferris", "and another thing").replying_to(id - 1));
+        }
+
+        let threads = builder.build();
+        assert_eq!(1, threads.len());
+        assert_eq!(DEPTH as usize, threads[0].tweet_count());
+
+        let mut lines = Lines(0);
+        fmt::write(&mut lines, format_args!("{}", threads[0])).unwrap();
+        assert_eq!(DEPTH, lines.0);
+
+        drop(threads);
+    }
+
+    #[test]
+    fn tweet_display_names_its_parent() {
+        assert_eq!(
+            "#2 @ferris: indeed (reply to #1)",
+            tweet(2, "ferris", "indeed").replying_to(1).to_string()
+        );
+        assert_eq!(
+            "#3 @bors retweeted #1",
+            tweet(3, "bors", "").retweeting(1).to_string()
+        );
+    }
+}
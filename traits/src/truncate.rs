@@ -0,0 +1,73 @@
+// Lengths here count grapheme clusters, the characters a reader sees, so an
+// emoji made of several code points or an "e" with a combining accent counts
+// as one and is never cut in half.
+
+use unicode_segmentation::UnicodeSegmentation;
+
+pub const ELLIPSIS: &str = "…";
+
+/// How many characters a reader sees in `text`.
+pub fn length(text: &str) -> usize {
+    text.graphemes(true).count()
+}
+
+/// `text` if it fits in `max_chars`, otherwise as many whole words as fit
+/// followed by an ellipsis, which counts towards `max_chars`. A first word
+/// that is too long on its own is cut between two characters instead.
+pub fn truncate(text: &str, max_chars: usize) -> String {
+    let graphemes: Vec<&str> = text.graphemes(true).collect();
+
+    if graphemes.len() <= max_chars {
+        return text.to_string();
+    }
+    if max_chars == 0 {
+        return String::new();
+    }
+
+    let is_space = |grapheme: &str| grapheme.chars().all(char::is_whitespace);
+    let mut end = max_chars - 1;
+
+    // Unless the cut happens to fall just before a space, go back to the
+    // last space so no word is left half-written.
+    if !is_space(graphemes[end]) {
+        if let Some(space) = graphemes[..end].iter().rposition(|g| is_space(g)) {
+            end = space;
+        }
+    }
+
+    let kept = graphemes[..end].concat();
+    format!("{}{ELLIPSIS}", kept.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_text_is_unchanged() {
+        assert_eq!("of course", truncate("of course", 9));
+    }
+
+    #[test]
+    fn cuts_at_a_word_boundary() {
+        assert_eq!("of course, as…", truncate("of course, as you know", 16));
+        assert_eq!("of course,…", truncate("of course, as you know", 12));
+    }
+
+    #[test]
+    fn cuts_a_single_long_word_between_characters() {
+        assert_eq!("abcd…", truncate("abcdefghij", 5));
+        assert_eq!("", truncate("abcdefghij", 0));
+    }
+
+    #[test]
+    fn never_splits_a_grapheme() {
+        // "é" written as e plus a combining accent, and a family emoji made of
+        // several code points joined together.
+        let text = "cafe\u{301} 👨‍👩‍👧 ok";
+
+        assert_eq!(9, length(text));
+        assert_eq!("cafe\u{301} 👨‍👩‍👧…", truncate(text, 7));
+        assert_eq!("cafe\u{301}…", truncate(text, 6));
+    }
+}